@@ -0,0 +1,93 @@
+use crate::matcher::class::{next_char, previous_char, range_size};
+
+/// A partition of all chars into disjoint ranges, called atoms. Every char set an automaton
+/// uses is a union of atoms, so automata can work on atom indexes instead of chars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alphabet {
+    atoms: Vec<(char, char)>
+}
+
+impl Alphabet {
+    /// Creates the coarsest partition in which every given char set is a union of atoms.
+    pub fn new(char_sets: &[Vec<(char, char)>]) -> Self {
+        let mut boundaries: Vec<char> = vec!['\0'];
+        for (start, end) in char_sets.iter().flatten() {
+            boundaries.push(*start);
+            if let Some(next) = next_char(*end) {
+                boundaries.push(next);
+            }
+        }
+        boundaries.sort();
+        boundaries.dedup();
+
+        let atoms = boundaries.iter().enumerate()
+            .map(|(index, start)| match boundaries.get(index + 1) {
+                Some(next_start) => (*start, previous_char(*next_start)),
+                None => (*start, char::MAX)
+            })
+            .collect();
+        Alphabet { atoms }
+    }
+
+    pub fn len(&self) -> usize {
+        self.atoms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.atoms.is_empty()
+    }
+
+    pub fn atoms(&self) -> &[(char, char)] {
+        &self.atoms
+    }
+
+    /// Returns the index of the atom containing the given char.
+    pub fn atom_of(&self, c: char) -> usize {
+        self.atoms.partition_point(|(_, end)| *end < c)
+    }
+
+    /// Returns the indexes of all atoms the given char set consists of.
+    pub fn atoms_of(&self, char_set: &[(char, char)]) -> Vec<usize> {
+        let mut atoms: Vec<usize> = char_set.iter()
+            .filter(|(start, end)| start <= end)
+            .flat_map(|(start, end)| self.atom_of(*start)..=self.atom_of(*end))
+            .collect();
+        atoms.sort_unstable();
+        atoms.dedup();
+        atoms
+    }
+
+    /// Returns a char that stands for the atom with the given index.
+    pub fn representative(&self, atom: usize) -> char {
+        self.atoms[atom].0
+    }
+
+    /// Returns how many chars the atom with the given index contains.
+    pub fn atom_size(&self, atom: usize) -> u32 {
+        let (start, end) = self.atoms[atom];
+        range_size(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automaton::alphabet::Alphabet;
+
+    #[test]
+    fn success_new() {
+        let alphabet = Alphabet::new(&[vec![('a', 'c')], vec![('b', 'b'), ('x', 'x')]]);
+
+        assert_eq!(&[('\0', '`'), ('a', 'a'), ('b', 'b'), ('c', 'c'), ('d', 'w'), ('x', 'x'), ('y', char::MAX)], alphabet.atoms());
+        assert_eq!(vec![1, 2, 3], alphabet.atoms_of(&[('a', 'c')]));
+        assert_eq!(4, alphabet.atom_of('q'));
+        assert_eq!(20, alphabet.atom_size(4))
+    }
+
+    #[test]
+    fn success_atom_size_without_surrogates() {
+        let alphabet = Alphabet::new(&[]);
+
+        assert_eq!(1, alphabet.len());
+        assert_eq!(0x10FFFF + 1 - 0x800, alphabet.atom_size(0))
+    }
+}
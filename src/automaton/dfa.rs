@@ -0,0 +1,326 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
+
+/// A state of two automata run side by side.
+type StatePair = (usize, usize);
+
+/// Tells for every reached StatePair from which one and by which atom it was reached first.
+type Predecessors = HashMap<StatePair, Option<(StatePair, usize)>>;
+
+/// A deterministic finite automaton over the atoms of an Alphabet. Every state has
+/// a transition for every atom, so a string that leaves the language ends in a dead state.
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    alphabet: Alphabet,
+    transitions: Vec<Vec<usize>>,
+    accepting: Vec<bool>,
+    start: usize,
+}
+
+impl Dfa {
+    /// Turns the given fragment of the Nfa into a Dfa by subset construction.
+    /// Fails if this takes more states than the given limit.
+    pub fn from_nfa(nfa: &Nfa, fragment: Fragment, alphabet: Alphabet, state_limit: usize) -> Result<Self, AutomatonError> {
+        let start_set = epsilon_closure(nfa, vec![fragment.start]);
+        let mut state_ids: HashMap<Vec<usize>, usize> = HashMap::new();
+        let mut state_sets: Vec<Vec<usize>> = vec![];
+        let mut transitions: Vec<Vec<usize>> = vec![];
+
+        state_ids.insert(start_set.clone(), 0);
+        state_sets.push(start_set);
+
+        let mut current = 0;
+        while current < state_sets.len() {
+            let mut targets: Vec<Vec<usize>> = vec![vec![]; alphabet.len()];
+            for nfa_state in &state_sets[current] {
+                for (atoms, target) in nfa.transitions(*nfa_state) {
+                    for atom in atoms {
+                        targets[*atom].push(*target);
+                    }
+                }
+            }
+
+            let mut row = Vec::with_capacity(alphabet.len());
+            for target in targets {
+                let target_set = epsilon_closure(nfa, target);
+                let id = match state_ids.get(&target_set) {
+                    Some(id) => *id,
+                    None => {
+                        if state_sets.len() == state_limit {
+                            return Err(AutomatonError::StateLimitExceeded(state_limit));
+                        }
+                        state_ids.insert(target_set.clone(), state_sets.len());
+                        state_sets.push(target_set);
+                        state_sets.len() - 1
+                    }
+                };
+                row.push(id);
+            }
+            transitions.push(row);
+            current += 1;
+        }
+
+        let accepting = state_sets.iter().map(|set| set.binary_search(&fragment.end).is_ok()).collect();
+        Ok(Dfa { alphabet, transitions, accepting, start: 0 })
+    }
+
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn is_accepting(&self, state: usize) -> bool {
+        self.accepting[state]
+    }
+
+    /// Returns the target state of every atom, ordered by atom.
+    pub fn transitions(&self, state: usize) -> &[usize] {
+        &self.transitions[state]
+    }
+
+    /// Returns if this Dfa accepts the given string.
+    pub fn accepts(&self, string: &str) -> bool {
+        let end = string.chars().fold(self.start, |state, c| self.transitions[state][self.alphabet.atom_of(c)]);
+        self.accepting[end]
+    }
+
+    /// Returns a Dfa accepting exactly the strings this one rejects.
+    pub fn complement(&self) -> Dfa {
+        Dfa {
+            accepting: self.accepting.iter().map(|accepting| !accepting).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a Dfa accepting the strings both this and the other Dfa accept.
+    /// Both must use the same Alphabet.
+    pub fn intersection(&self, other: &Dfa, state_limit: usize) -> Result<Dfa, AutomatonError> {
+        let mut state_ids: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut pairs = vec![(self.start, other.start)];
+        let mut transitions: Vec<Vec<usize>> = vec![];
+        state_ids.insert(pairs[0], 0);
+
+        let mut current = 0;
+        while current < pairs.len() {
+            let (left, right) = pairs[current];
+            let mut row = Vec::with_capacity(self.alphabet.len());
+            for atom in 0..self.alphabet.len() {
+                let pair = (self.transitions[left][atom], other.transitions[right][atom]);
+                let id = match state_ids.get(&pair) {
+                    Some(id) => *id,
+                    None => {
+                        if pairs.len() == state_limit {
+                            return Err(AutomatonError::StateLimitExceeded(state_limit));
+                        }
+                        state_ids.insert(pair, pairs.len());
+                        pairs.push(pair);
+                        pairs.len() - 1
+                    }
+                };
+                row.push(id);
+            }
+            transitions.push(row);
+            current += 1;
+        }
+
+        let accepting = pairs.iter().map(|(left, right)| self.accepting[*left] && other.accepting[*right]).collect();
+        Ok(Dfa { alphabet: self.alphabet.clone(), transitions, accepting, start: 0 })
+    }
+
+    /// Searches the shortest string for which the given function, called with whether this
+    /// and the other Dfa accept it, returns true. Both must use the same Alphabet.
+    /// Among strings of equal length, the one with the smallest chars is returned.
+    pub fn shortest_string_where(&self, other: &Dfa, condition: impl Fn(bool, bool) -> bool) -> Option<String> {
+        let start = (self.start, other.start);
+        let mut reached_by: Predecessors = HashMap::new();
+        let mut queue = VecDeque::from(vec![start]);
+        reached_by.insert(start, None);
+
+        while let Some(pair) = queue.pop_front() {
+            if condition(self.accepting[pair.0], other.accepting[pair.1]) {
+                return Some(self.path_to(pair, &reached_by));
+            }
+
+            for atom in 0..self.alphabet.len() {
+                let next = (self.transitions[pair.0][atom], other.transitions[pair.1][atom]);
+                if let std::collections::hash_map::Entry::Vacant(entry) = reached_by.entry(next) {
+                    entry.insert(Some((pair, atom)));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns how many strings of the given length this Dfa accepts,
+    /// or None if there are more than fit into a u128.
+    pub fn count_of_length(&self, length: usize) -> Option<u128> {
+        // counts[state] is the number of strings of the current length leading from state to acceptance
+        let mut counts: Vec<Option<u128>> = self.accepting.iter().map(|accepting| Some(*accepting as u128)).collect();
+        for _ in 0..length {
+            counts = (0..self.state_count())
+                .map(|state| self.transitions[state].iter().enumerate().try_fold(0u128, |sum, (atom, target)| {
+                    let strings = counts[*target]?.checked_mul(self.alphabet.atom_size(atom) as u128)?;
+                    sum.checked_add(strings)
+                }))
+                .collect();
+        }
+        counts[self.start]
+    }
+
+    /// Returns if this Dfa accepts only finitely many strings, which is the case
+    /// if no state on the way from the start to acceptance lies on a cycle.
+    pub fn is_finite(&self) -> bool {
+        let reachable = self.reachable_from_start();
+        let live = self.leading_to_acceptance();
+        let relevant: Vec<bool> = (0..self.state_count()).map(|state| reachable[state] && live[state]).collect();
+
+        // Depth first search for a cycle, 1 marks states on the current path, 2 finished ones
+        let mut marks = vec![0u8; self.state_count()];
+        for root in (0..self.state_count()).filter(|state| relevant[*state]) {
+            if marks[root] != 0 {
+                continue;
+            }
+            let mut stack = vec![(root, 0)];
+            marks[root] = 1;
+            while let Some((state, next_atom)) = stack.pop() {
+                if next_atom == self.alphabet.len() {
+                    marks[state] = 2;
+                    continue;
+                }
+                stack.push((state, next_atom + 1));
+
+                let target = self.transitions[state][next_atom];
+                if !relevant[target] {
+                    continue;
+                }
+                match marks[target] {
+                    0 => {
+                        marks[target] = 1;
+                        stack.push((target, 0));
+                    }
+                    1 => return false,
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn reachable_from_start(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.state_count()];
+        let mut stack = vec![self.start];
+        reachable[self.start] = true;
+        while let Some(state) = stack.pop() {
+            for target in &self.transitions[state] {
+                if !reachable[*target] {
+                    reachable[*target] = true;
+                    stack.push(*target);
+                }
+            }
+        }
+        reachable
+    }
+
+    fn leading_to_acceptance(&self) -> Vec<bool> {
+        let mut predecessors: Vec<Vec<usize>> = vec![vec![]; self.state_count()];
+        for (state, row) in self.transitions.iter().enumerate() {
+            for target in row {
+                predecessors[*target].push(state);
+            }
+        }
+
+        let mut live = self.accepting.clone();
+        let mut stack: Vec<usize> = (0..self.state_count()).filter(|state| live[*state]).collect();
+        while let Some(state) = stack.pop() {
+            for predecessor in &predecessors[state] {
+                if !live[*predecessor] {
+                    live[*predecessor] = true;
+                    stack.push(*predecessor);
+                }
+            }
+        }
+        live
+    }
+
+    fn path_to(&self, mut pair: StatePair, reached_by: &Predecessors) -> String {
+        let mut chars = vec![];
+        while let Some(Some((previous, atom))) = reached_by.get(&pair) {
+            chars.push(self.alphabet.representative(*atom));
+            pair = *previous;
+        }
+        chars.iter().rev().collect()
+    }
+}
+
+/// Returns the given states and all states reachable from them without consuming a char, sorted.
+fn epsilon_closure(nfa: &Nfa, mut states: Vec<usize>) -> Vec<usize> {
+    let mut closure: Vec<usize> = vec![];
+    let mut seen = vec![false; nfa.state_count()];
+    while let Some(state) = states.pop() {
+        if seen[state] {
+            continue;
+        }
+        seen[state] = true;
+        closure.push(state);
+        states.extend_from_slice(nfa.epsilons(state));
+    }
+    closure.sort_unstable();
+    closure
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automaton::alphabet::Alphabet;
+    use crate::automaton::dfa::Dfa;
+    use crate::automaton::DEFAULT_STATE_LIMIT;
+    use crate::automaton::nfa::Nfa;
+
+    fn ab_star() -> Dfa {
+        let alphabet = Alphabet::new(&[vec![('a', 'b')]]);
+        let mut nfa = Nfa::new();
+        let atoms = nfa.atoms(alphabet.atoms_of(&[('a', 'b')]));
+        let fragment = nfa.star(atoms);
+        Dfa::from_nfa(&nfa, fragment, alphabet, DEFAULT_STATE_LIMIT).unwrap()
+    }
+
+    #[test]
+    fn success_from_nfa() {
+        let dfa = ab_star();
+
+        assert!(dfa.accepts(""));
+        assert!(dfa.accepts("abba"));
+        assert!(!dfa.accepts("abc"));
+        assert!(!dfa.complement().accepts("ab"))
+    }
+
+    #[test]
+    fn success_shortest_string_where() {
+        let dfa = ab_star();
+
+        assert_eq!(Some(String::from("\0")), dfa.shortest_string_where(&dfa, |left, _| !left));
+        assert_eq!(Some(String::from("")), dfa.shortest_string_where(&dfa.complement(), |left, right| left && !right));
+        assert_eq!(None, dfa.shortest_string_where(&dfa, |left, right| left != right))
+    }
+
+    #[test]
+    fn failure_state_limit_exceeded() {
+        let alphabet = Alphabet::new(&[]);
+        let mut nfa = Nfa::new();
+        let first = nfa.atoms(vec![0]);
+        let second = nfa.atoms(vec![0]);
+        let fragment = nfa.concat(first, second);
+
+        assert!(Dfa::from_nfa(&nfa, fragment, alphabet, 2).is_err())
+    }
+}
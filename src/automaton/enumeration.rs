@@ -0,0 +1,135 @@
+use crate::automaton::dfa::Dfa;
+
+/// Iterates over every string a Dfa accepts, up to a maximum length, in shortlex order:
+/// shorter strings first, strings of the same length ordered by their chars.
+/// Only chars of the given alphabet are used.
+pub struct Enumeration {
+    dfa: Dfa,
+    alphabet: Vec<(char, usize)>,
+    max_length: usize,
+    accepts_in: Vec<Vec<bool>>,
+    next_length: usize,
+    length: usize,
+    stack: Vec<(usize, usize)>,
+    prefix: Vec<char>,
+}
+
+impl Enumeration {
+    pub fn new(dfa: Dfa, alphabet: &[char], max_length: usize) -> Self {
+        let mut chars = alphabet.to_vec();
+        chars.sort_unstable();
+        chars.dedup();
+        let alphabet: Vec<(char, usize)> = chars.into_iter().map(|c| (c, dfa.alphabet().atom_of(c))).collect();
+
+        // accepts_in[k][state] tells if some string of length k leads from state to acceptance
+        let mut accepts_in: Vec<Vec<bool>> = vec![(0..dfa.state_count()).map(|state| dfa.is_accepting(state)).collect()];
+        for k in 1..=max_length {
+            let row = (0..dfa.state_count())
+                .map(|state| alphabet.iter().any(|(_, atom)| accepts_in[k - 1][dfa.transitions(state)[*atom]]))
+                .collect();
+            accepts_in.push(row);
+        }
+
+        Enumeration { dfa, alphabet, max_length, accepts_in, next_length: 0, length: 0, stack: vec![], prefix: vec![] }
+    }
+
+    /// Returns the chars used, sorted.
+    pub fn alphabet(&self) -> Vec<char> {
+        self.alphabet.iter().map(|(c, _)| *c).collect()
+    }
+
+    /// Creates a new Enumeration like this one, using the chars of the given string as alphabet.
+    pub fn with_alphabet(self, alphabet: &str) -> Self {
+        Enumeration::new(self.dfa, &alphabet.chars().collect::<Vec<char>>(), self.max_length)
+    }
+}
+
+impl Iterator for Enumeration {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let (state, next_index) = match self.stack.last() {
+                Some(top) => *top,
+                None => {
+                    if self.next_length > self.max_length {
+                        return None;
+                    }
+                    self.length = self.next_length;
+                    self.next_length += 1;
+                    if self.accepts_in[self.length][self.dfa.start()] {
+                        self.stack.push((self.dfa.start(), 0));
+                    }
+                    continue;
+                }
+            };
+
+            let remaining = self.length - self.prefix.len();
+            if remaining == 0 {
+                let string = self.prefix.iter().collect();
+                self.stack.pop();
+                self.prefix.pop();
+                return Some(string);
+            }
+
+            let found = self.alphabet.iter().enumerate().skip(next_index)
+                .map(|(index, (c, atom))| (index, *c, self.dfa.transitions(state)[*atom]))
+                .find(|(_, _, target)| self.accepts_in[remaining - 1][*target]);
+
+            match found {
+                Some((index, c, target)) => {
+                    if let Some(top) = self.stack.last_mut() {
+                        top.1 = index + 1;
+                    }
+                    self.stack.push((target, 0));
+                    self.prefix.push(c);
+                }
+                None => {
+                    self.stack.pop();
+                    self.prefix.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::matcher::class::Class;
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_enumerate_finite_language() {
+        let regex = (Regex::from("x") | Regex::matcher(Class::of("ab"))).that_repeats(Times::new(2));
+        let strings: Vec<String> = regex.enumerate(10).unwrap().collect();
+
+        assert_eq!(9, strings.len());
+        assert_eq!(vec!["aa", "ab", "ax", "ba"], strings[..4].to_vec())
+    }
+
+    #[test]
+    fn success_enumerate_in_shortlex_order() {
+        let regex = (Regex::from("b") + Regex::from("a").that_repeats(ZeroToInfinity)) | "ab";
+        let strings: Vec<String> = regex.enumerate(3).unwrap().collect();
+
+        assert_eq!(vec!["b", "ab", "ba", "baa"], strings)
+    }
+
+    #[test]
+    fn success_enumerate_with_alphabet() {
+        let regex = Regex::and(Regex::matcher(Any).that_repeats(ZeroToInfinity), Regex::not(Regex::from("01")));
+        let strings: Vec<String> = regex.enumerate(2).unwrap().with_alphabet("10").collect();
+
+        assert_eq!(vec!["", "0", "1", "00", "10", "11"], strings)
+    }
+
+    #[test]
+    fn success_enumerate_default_alphabet() {
+        let enumeration = (Regex::from("é") | Regex::matcher(Class::digit())).enumerate(1).unwrap();
+
+        assert_eq!("0123456789é".chars().collect::<Vec<char>>(), enumeration.alphabet())
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::Formatter;
+
+pub mod alphabet;
+pub mod nfa;
+pub mod dfa;
+pub mod enumeration;
+
+/// How many states an automaton built from a regex may have, before building it is aborted.
+pub const DEFAULT_STATE_LIMIT: usize = 10_000;
+
+/// Everything that can keep a regex from being turned into an automaton.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutomatonError {
+    /// The regex contains the given node, which needs more than a finite automaton,
+    /// like a backreference, a lookaround, a rule reference or a custom matcher.
+    Unsupported(String),
+    /// The automaton would need more states than the given limit.
+    StateLimitExceeded(usize),
+}
+
+impl std::error::Error for AutomatonError {}
+
+impl std::fmt::Display for AutomatonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported(node) => write!(f, "{} cannot be turned into an automaton!", node),
+            Self::StateLimitExceeded(limit) => write!(f, "The automaton exceeded the limit of {} states!", limit)
+        }
+    }
+}
+
+/// The answer to a question about the languages of two regexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Holds,
+    /// The question is answered with no, proven by the given shortest string.
+    Counterexample(String),
+}
+
+impl Verdict {
+    pub fn holds(&self) -> bool {
+        *self == Verdict::Holds
+    }
+
+    pub fn counterexample(&self) -> Option<&str> {
+        match self {
+            Verdict::Holds => None,
+            Verdict::Counterexample(string) => Some(string)
+        }
+    }
+}
+
+/// How many strings of one length a regex matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchCount {
+    /// The number of matched strings, or None if it does not fit into a u128.
+    pub count: Option<u128>,
+    /// If the regex matches only finitely many strings of all lengths together.
+    pub finite: bool,
+}
+
+impl MatchCount {
+    pub fn overflowed(&self) -> bool {
+        self.count.is_none()
+    }
+}
@@ -0,0 +1,125 @@
+use crate::automaton::dfa::Dfa;
+
+/// A part of an Nfa with a single start and a single end state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fragment {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A nondeterministic finite automaton over the atoms of an Alphabet,
+/// built fragment by fragment (Thompson's construction).
+#[derive(Debug, Default)]
+pub struct Nfa {
+    epsilons: Vec<Vec<usize>>,
+    transitions: Vec<Vec<(Vec<usize>, usize)>>,
+}
+
+impl Nfa {
+    pub fn new() -> Self {
+        Nfa::default()
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.epsilons.len()
+    }
+
+    pub fn add_state(&mut self) -> usize {
+        self.epsilons.push(vec![]);
+        self.transitions.push(vec![]);
+        self.epsilons.len() - 1
+    }
+
+    pub fn add_epsilon(&mut self, from: usize, to: usize) {
+        self.epsilons[from].push(to)
+    }
+
+    pub fn add_transition(&mut self, from: usize, atoms: Vec<usize>, to: usize) {
+        self.transitions[from].push((atoms, to))
+    }
+
+    pub fn epsilons(&self, state: usize) -> &[usize] {
+        &self.epsilons[state]
+    }
+
+    pub fn transitions(&self, state: usize) -> &[(Vec<usize>, usize)] {
+        &self.transitions[state]
+    }
+
+    /// A fragment that matches only the empty string.
+    pub fn empty(&mut self) -> Fragment {
+        let start = self.add_state();
+        let end = self.add_state();
+        self.add_epsilon(start, end);
+        Fragment { start, end }
+    }
+
+    /// A fragment that matches one char out of the given atoms.
+    pub fn atoms(&mut self, atoms: Vec<usize>) -> Fragment {
+        let start = self.add_state();
+        let end = self.add_state();
+        self.add_transition(start, atoms, end);
+        Fragment { start, end }
+    }
+
+    /// A fragment that matches the first fragment followed by the second.
+    pub fn concat(&mut self, first: Fragment, second: Fragment) -> Fragment {
+        self.add_epsilon(first.end, second.start);
+        Fragment { start: first.start, end: second.end }
+    }
+
+    /// A fragment that matches either the left or the right fragment.
+    pub fn union(&mut self, left: Fragment, right: Fragment) -> Fragment {
+        let start = self.add_state();
+        let end = self.add_state();
+        self.add_epsilon(start, left.start);
+        self.add_epsilon(start, right.start);
+        self.add_epsilon(left.end, end);
+        self.add_epsilon(right.end, end);
+        Fragment { start, end }
+    }
+
+    /// A fragment that matches the given fragment or the empty string.
+    pub fn optional(&mut self, fragment: Fragment) -> Fragment {
+        let empty = self.empty();
+        self.union(fragment, empty)
+    }
+
+    /// A fragment that matches the given fragment any number of times.
+    pub fn star(&mut self, fragment: Fragment) -> Fragment {
+        let start = self.add_state();
+        let end = self.add_state();
+        self.add_epsilon(start, fragment.start);
+        self.add_epsilon(start, end);
+        self.add_epsilon(fragment.end, fragment.start);
+        self.add_epsilon(fragment.end, end);
+        Fragment { start, end }
+    }
+
+    /// A fragment that matches what the given Dfa accepts.
+    pub fn embed(&mut self, dfa: &Dfa) -> Fragment {
+        let offset = self.state_count();
+        for _ in 0..dfa.state_count() {
+            self.add_state();
+        }
+        let end = self.add_state();
+
+        for state in 0..dfa.state_count() {
+            let mut targets: Vec<(usize, Vec<usize>)> = vec![];
+            for (atom, target) in dfa.transitions(state).iter().enumerate() {
+                match targets.iter_mut().find(|(existing, _)| existing == target) {
+                    Some((_, atoms)) => atoms.push(atom),
+                    None => targets.push((*target, vec![atom]))
+                }
+            }
+            for (target, atoms) in targets {
+                self.add_transition(offset + state, atoms, offset + target);
+            }
+            if dfa.is_accepting(state) {
+                self.add_epsilon(offset + state, end);
+            }
+        }
+
+        Fragment { start: offset + dfa.start(), end }
+    }
+}
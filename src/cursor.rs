@@ -0,0 +1,123 @@
+/// A position in a sequence of items of type T, which regexes match against.
+/// Matching moves the cursor forward and backtracking sets it back.
+///
+/// A cursor either reads tokens, which predicates like Regex::token test,
+/// or text, which matchers like Str test. StringPointer is the cursor for text.
+pub trait Cursor<T> {
+    /// Returns the current index, counted in items.
+    fn index(&self) -> usize;
+
+    /// Moves the cursor to the given index. Indexes behind the end are clamped to the end.
+    fn set_index(&mut self, index: usize);
+
+    /// Returns the number of all items.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns if the cursor points to the end.
+    fn at_the_end(&self) -> bool {
+        self.index() == self.len()
+    }
+
+    /// Returns the item at the current index and moves past it. Returns None
+    /// at the end, or if this cursor reads text instead of tokens.
+    fn next_token(&mut self) -> Option<&T>;
+
+    /// Returns the next given amount of chars and moves past them. Returns None
+    /// if there are not enough left, or if this cursor reads tokens instead of text.
+    fn take_text(&mut self, amount: usize) -> Option<String>;
+
+    /// Returns the text between the given start and end index, if this cursor reads text.
+    fn text(&self, start: usize, end: usize) -> Option<String>;
+
+    /// Returns if the items between the given start and end index follow at the current
+    /// index again. If so, the cursor moves past them.
+    fn repeats(&mut self, start: usize, end: usize) -> bool;
+}
+
+/// A cursor over a slice of tokens, like the output of a lexer or a list of events.
+pub struct TokenCursor<'t, T> {
+    tokens: &'t [T],
+    index: usize,
+}
+
+impl<'t, T> TokenCursor<'t, T> {
+    pub fn new(tokens: &'t [T]) -> Self {
+        TokenCursor {
+            tokens,
+            index: 0
+        }
+    }
+}
+
+impl<'t, T: PartialEq> Cursor<T> for TokenCursor<'t, T> {
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn set_index(&mut self, index: usize) {
+        self.index = index.min(self.tokens.len())
+    }
+
+    fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    fn next_token(&mut self) -> Option<&T> {
+        let token = self.tokens.get(self.index)?;
+        self.index += 1;
+        Some(token)
+    }
+
+    fn take_text(&mut self, _amount: usize) -> Option<String> {
+        None
+    }
+
+    fn text(&self, _start: usize, _end: usize) -> Option<String> {
+        None
+    }
+
+    fn repeats(&mut self, start: usize, end: usize) -> bool {
+        let length = end.saturating_sub(start);
+        let repeated = self.tokens.get(start..end);
+        let following = self.tokens.get(self.index..self.index + length);
+
+        let matches = repeated.is_some() && repeated == following;
+        if matches {
+            self.index += length;
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cursor::{Cursor, TokenCursor};
+
+    #[test]
+    fn success_next_token() {
+        let tokens = [1, 2];
+        let mut cursor = TokenCursor::new(&tokens);
+
+        assert_eq!(Some(&1), cursor.next_token());
+        assert_eq!(Some(&2), cursor.next_token());
+        assert_eq!(None, cursor.next_token());
+        assert!(cursor.at_the_end());
+        assert_eq!(None, cursor.take_text(1))
+    }
+
+    #[test]
+    fn success_repeats() {
+        let tokens = ["a", "b", "a", "b", "a"];
+        let mut cursor = TokenCursor::new(&tokens);
+        cursor.set_index(2);
+
+        assert!(cursor.repeats(0, 2));
+        assert_eq!(4, cursor.index());
+        assert!(!cursor.repeats(0, 2));
+        assert_eq!(4, cursor.index())
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use crate::regex::Regex;
+use crate::regex::rule_check::RuleBody;
+use crate::repeat::Repeat;
+
+/// A parsing expression, the building block of the rules of a Grammar.
+pub enum Expression<'a> {
+    /// Matches a Regex as a token. The first match the Regex finds is taken.
+    Token(Regex<'a>),
+    /// Parses the rule with the given name.
+    Rule(String),
+    /// Parses the expressions one after another.
+    Sequence(Vec<Expression<'a>>),
+    /// Tries the expressions in order and takes the first one that succeeds.
+    /// Later alternatives are never tried once one succeeded.
+    Choice(Vec<Expression<'a>>),
+    /// Parses the expression as often as possible, between min and max times.
+    /// Repeats never give anything back.
+    Repeat { expression: Box<Expression<'a>>, min: usize, max: Option<usize> },
+    /// Succeeds without consuming anything, if the expression succeeds.
+    AndPredicate(Box<Expression<'a>>),
+    /// Succeeds without consuming anything, if the expression fails.
+    NotPredicate(Box<Expression<'a>>),
+}
+
+impl<'a> Expression<'a> {
+    pub fn token(regex: Regex<'a>) -> Self {
+        Expression::Token(regex)
+    }
+
+    pub fn rule(name: &str) -> Self {
+        Expression::Rule(String::from(name))
+    }
+
+    pub fn sequence(expressions: Vec<Expression<'a>>) -> Self {
+        Expression::Sequence(expressions)
+    }
+
+    pub fn choice(expressions: Vec<Expression<'a>>) -> Self {
+        Expression::Choice(expressions)
+    }
+
+    pub fn and_predicate(expression: Expression<'a>) -> Self {
+        Expression::AndPredicate(Box::new(expression))
+    }
+
+    pub fn not_predicate(expression: Expression<'a>) -> Self {
+        Expression::NotPredicate(Box::new(expression))
+    }
+
+    pub fn that_repeats(self, repeat: impl Repeat) -> Self {
+        Expression::Repeat {
+            expression: Box::new(self),
+            min: repeat.get_minimum().unwrap_or(0),
+            max: repeat.get_maximum(),
+        }
+    }
+}
+
+/// The rules of a Grammar are checked like the rules of a RuleSet.
+impl<'a> RuleBody for Expression<'a> {
+    fn referenced_rules(&self) -> Vec<String> {
+        match self {
+            Expression::Token(_) => vec![],
+            Expression::Rule(name) => vec![name.clone()],
+            Expression::Sequence(expressions) | Expression::Choice(expressions) => expressions.iter()
+                .flat_map(|expression| expression.referenced_rules())
+                .collect(),
+            Expression::Repeat { expression, .. }
+            | Expression::AndPredicate(expression)
+            | Expression::NotPredicate(expression) => expression.referenced_rules()
+        }
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        match self {
+            Expression::Token(regex) => regex.nullable(&HashMap::new()),
+            Expression::Rule(name) => nullable_rules.get(name).copied().unwrap_or(false),
+            Expression::Sequence(expressions) => expressions.iter().all(|expression| expression.nullable(nullable_rules)),
+            Expression::Choice(expressions) => expressions.iter().any(|expression| expression.nullable(nullable_rules)),
+            Expression::Repeat { expression, min, .. } => *min == 0 || expression.nullable(nullable_rules),
+            Expression::AndPredicate(_) | Expression::NotPredicate(_) => true
+        }
+    }
+
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        match self {
+            Expression::Token(_) => vec![],
+            Expression::Rule(name) => vec![name.clone()],
+            Expression::Sequence(expressions) => {
+                let mut leading_rules = vec![];
+                for expression in expressions {
+                    leading_rules.extend(expression.leading_rules(nullable_rules));
+                    if !expression.nullable(nullable_rules) {
+                        break;
+                    }
+                }
+                leading_rules
+            }
+            Expression::Choice(expressions) => expressions.iter()
+                .flat_map(|expression| expression.leading_rules(nullable_rules))
+                .collect(),
+            Expression::Repeat { expression, .. }
+            | Expression::AndPredicate(expression)
+            | Expression::NotPredicate(expression) => expression.leading_rules(nullable_rules)
+        }
+    }
+}
+
+/// Creates a token from the given Regex.
+impl<'a> From<Regex<'a>> for Expression<'a> {
+    fn from(regex: Regex<'a>) -> Self {
+        Expression::Token(regex)
+    }
+}
+
+/// Creates a token that matches the given literal.
+impl<'a> From<&str> for Expression<'a> {
+    fn from(string: &str) -> Self {
+        Expression::Token(Regex::from(string))
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt::Formatter;
+
+use crate::grammar::expression::Expression;
+use crate::grammar::parser::Parser;
+use crate::grammar::syntax_tree::SyntaxNode;
+use crate::regex::diagnosis::Diagnosis;
+use crate::regex::DEFAULT_RECURSION_LIMIT;
+use crate::regex::outcome::MatchError;
+use crate::regex::rule_check::check_rules;
+use crate::regex::rules::RuleError;
+
+pub mod expression;
+pub mod syntax_tree;
+mod parser;
+
+/// A parsing expression grammar (PEG): named rules built from ordered choice, sequences,
+/// repetition and predicates, with regexes as tokens. Parsing creates a concrete syntax tree.
+///
+/// Rules that could parse themselves again without consuming a char (left recursion)
+/// are rejected when the Grammar is created, so parsing always terminates.
+///
+/// Examples:
+/// ```
+/// use crate::readex::grammar::Grammar;
+/// use crate::readex::grammar::expression::Expression;
+/// use crate::readex::regex::Regex;
+/// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+///
+/// let grammar = Grammar::new(vec![
+///     ("sum", Expression::sequence(vec![
+///         Expression::rule("number"),
+///         Expression::sequence(vec![Expression::from("+"), Expression::rule("number")]).that_repeats(ZeroToInfinity),
+///     ])),
+///     ("number", Expression::choice(vec![Expression::from("1"), Expression::from("2")])),
+/// ]).unwrap();
+///
+/// let tree = grammar.parse("sum", "1+2+1").unwrap();
+/// assert_eq!(3, tree.children_of_rule("number").count());
+/// assert_eq!("at column 3: expected `1` or `2`, found `3`", grammar.parse("sum", "1+3").unwrap_err().to_string());
+/// ```
+pub struct Grammar<'a> {
+    rules: Vec<(String, Expression<'a>)>,
+    packrat: bool,
+    recursion_limit: usize,
+}
+
+impl<'a> Grammar<'a> {
+    pub fn new(rules: Vec<(&str, Expression<'a>)>) -> Result<Self, GrammarError> {
+        let grammar = Grammar {
+            rules: rules.into_iter().map(|(name, expression)| (String::from(name), expression)).collect(),
+            packrat: false,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+        };
+
+        check_rules(&grammar.rules)?;
+        Ok(grammar)
+    }
+
+    /// Enables or disables memoizing the result of every rule at every position (packrat parsing).
+    /// This makes parsing take linear time, at the cost of memory.
+    pub fn with_packrat(mut self, packrat: bool) -> Self {
+        self.packrat = packrat;
+        self
+    }
+
+    /// Sets how deep rules and tokens may nest while parsing before it fails with
+    /// MatchError::RecursionLimitExceeded, which is DEFAULT_RECURSION_LIMIT otherwise.
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Parses the whole input with the rule of the given name.
+    pub fn parse(&self, rule: &str, input: &str) -> Result<SyntaxNode, ParseError> {
+        Parser::new(self, input).parse(rule)
+    }
+
+    /// Returns the index of the rule with the given name.
+    fn rule_index(&self, name: &str) -> Option<usize> {
+        self.rules.iter().position(|(rule_name, _)| rule_name == name)
+    }
+}
+
+/// A Grammar is invalid for the same reasons as a RuleSet.
+pub type GrammarError = RuleError;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The input does not match the grammar.
+    Syntax(Diagnosis),
+    /// The rule to start parsing with does not exist.
+    UnknownRule(String),
+    /// Matching a token failed.
+    Match(MatchError),
+}
+
+impl From<MatchError> for ParseError {
+    fn from(error: MatchError) -> Self {
+        ParseError::Match(error)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(diagnosis) => write!(f, "{}", diagnosis),
+            Self::UnknownRule(rule) => write!(f, "There is no rule named {}!", rule),
+            Self::Match(error) => write!(f, "{}", error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::{Grammar, GrammarError, ParseError};
+    use crate::grammar::expression::Expression;
+    use crate::grammar::syntax_tree::SyntaxKind;
+    use crate::matcher::any::Any;
+    use crate::regex::outcome::MatchError;
+    use crate::regex::Regex;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    fn list_grammar<'a>() -> Grammar<'a> {
+        Grammar::new(vec![
+            ("value", Expression::choice(vec![Expression::rule("list"), Expression::rule("atom")])),
+            ("list", Expression::sequence(vec![
+                Expression::from("["),
+                Expression::rule("value"),
+                Expression::sequence(vec![Expression::from(","), Expression::rule("value")]).that_repeats(ZeroToInfinity),
+                Expression::from("]"),
+            ])),
+            ("atom", Expression::from(Regex::from("a") | "b")),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn success_parse() {
+        let input = "[a,[b,a]]";
+        let tree = list_grammar().parse("value", input).unwrap();
+
+        assert_eq!(SyntaxKind::Rule(String::from("value")), tree.kind);
+        assert_eq!((0, 9), (tree.start, tree.end));
+
+        let list = &tree.children[0];
+        let values: Vec<String> = list.children_of_rule("value").map(|value| value.text(input)).collect();
+        assert_eq!(vec![String::from("a"), String::from("[b,a]")], values);
+        assert_eq!(SyntaxKind::Token, list.children[0].kind)
+    }
+
+    #[test]
+    fn success_parse_with_packrat() {
+        let input = "[a,[b,a],[[a]]]";
+
+        assert_eq!(list_grammar().parse("value", input), list_grammar().with_packrat(true).parse("value", input))
+    }
+
+    #[test]
+    fn success_ordered_choice_does_not_backtrack() {
+        let grammar = Grammar::new(vec![
+            ("start", Expression::sequence(vec![
+                Expression::choice(vec![Expression::from("a"), Expression::from("ab")]),
+                Expression::from("c"),
+            ])),
+        ]).unwrap();
+
+        assert!(grammar.parse("start", "ac").is_ok());
+        assert!(grammar.parse("start", "abc").is_err())
+    }
+
+    #[test]
+    fn success_predicates() {
+        let comment_char = Expression::sequence(vec![Expression::not_predicate(Expression::from("*/")), Expression::from(Regex::matcher(Any))]);
+        let grammar = Grammar::new(vec![
+            ("comment", Expression::sequence(vec![Expression::from("/*"), comment_char.that_repeats(ZeroToInfinity), Expression::from("*/")])),
+            ("keyword", Expression::sequence(vec![Expression::from("if"), Expression::and_predicate(Expression::from(" ")), Expression::from(" ")])),
+        ]).unwrap();
+
+        assert!(grammar.parse("comment", "/* a * b */").is_ok());
+        assert!(grammar.parse("comment", "/* a */ b */").is_err());
+        assert!(grammar.parse("keyword", "if ").is_ok())
+    }
+
+    #[test]
+    fn failure_parse() {
+        let error = list_grammar().parse("value", "[a,c]").unwrap_err();
+
+        assert_eq!("at column 4: expected `[`, `a` or `b`, found `c`", error.to_string());
+        assert_eq!(Err(ParseError::UnknownRule(String::from("foo"))), list_grammar().parse("foo", "a"))
+    }
+
+    #[test]
+    fn failure_parse_with_packrat_reports_the_same() {
+        let grammar = || Grammar::new(vec![
+            ("start", Expression::choice(vec![
+                Expression::sequence(vec![Expression::not_predicate(Expression::rule("word")), Expression::from("x")]),
+                Expression::rule("word"),
+            ])),
+            ("word", Expression::sequence(vec![Expression::from("a"), Expression::from("b")])),
+        ]).unwrap();
+
+        let error = grammar().with_packrat(true).parse("start", "ac").unwrap_err();
+        assert_eq!("at column 2: expected `b`, found `c`", error.to_string());
+        assert_eq!(grammar().parse("start", "ac"), Err(error))
+    }
+
+    #[test]
+    fn failure_parse_recursion_limit() {
+        let input = format!("{}a{}", "[".repeat(20), "]".repeat(20));
+
+        assert!(list_grammar().with_recursion_limit(100).parse("value", &input).is_ok());
+        assert_eq!(Err(ParseError::Match(MatchError::RecursionLimitExceeded(10))), list_grammar().with_recursion_limit(10).parse("value", &input))
+    }
+
+    #[test]
+    fn failure_left_recursion() {
+        let result = Grammar::new(vec![
+            ("sum", Expression::choice(vec![Expression::sequence(vec![Expression::rule("sum"), Expression::from("+")]), Expression::from("1")])),
+        ]);
+
+        assert_eq!(Some(GrammarError::LeftRecursion(vec![String::from("sum"), String::from("sum")])), result.err())
+    }
+
+    #[test]
+    fn failure_unknown_rule() {
+        let result = Grammar::new(vec![
+            ("sum", Expression::rule("number")),
+        ]);
+
+        assert_eq!(Some(GrammarError::UnknownRule { rule: String::from("sum"), reference: String::from("number") }), result.err())
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::grammar::{Grammar, ParseError};
+use crate::grammar::expression::Expression;
+use crate::grammar::syntax_tree::{SyntaxKind, SyntaxNode};
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+
+/// The end position and the created nodes of a successfully parsed expression.
+type Parsed = Option<(usize, Vec<SyntaxNode>)>;
+
+/// Parses a single input with a Grammar.
+pub struct Parser<'g, 'a> {
+    grammar: &'g Grammar<'a>,
+    state: MatchState<'g, 'a>,
+    memo: HashMap<(usize, usize), Option<(usize, SyntaxNode)>>,
+}
+
+impl<'g, 'a> Parser<'g, 'a> {
+    pub fn new(grammar: &'g Grammar<'a>, input: &str) -> Self {
+        Parser {
+            grammar,
+            state: MatchState::new(input, grammar.recursion_limit),
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Parses the whole input with the rule of the given name.
+    pub fn parse(mut self, rule: &str) -> Result<SyntaxNode, ParseError> {
+        let rule_index = self.grammar.rule_index(rule).ok_or_else(|| ParseError::UnknownRule(String::from(rule)))?;
+
+        match self.parse_rule(rule_index, 0)? {
+            Some((end, node)) if end == self.state.cursor.len() => Ok(node),
+            Some((end, _)) => {
+                self.state.expect(end, || String::from("the end of the string"));
+                Err(ParseError::Syntax(self.state.diagnosis()))
+            }
+            None => Err(ParseError::Syntax(self.state.diagnosis()))
+        }
+    }
+
+    /// Parses the rule with the given index at the given position. With packrat parsing
+    /// enabled, every rule is only parsed once per position. Results parsed while silenced
+    /// are not remembered, as parsing them again must record what was expected.
+    fn parse_rule(&mut self, rule_index: usize, position: usize) -> Result<Option<(usize, SyntaxNode)>, MatchError> {
+        if let Some(parsed) = self.memo.get(&(rule_index, position)) {
+            return Ok(parsed.clone());
+        }
+
+        let (name, expression) = &self.grammar.rules[rule_index];
+        self.state.enter()?;
+        let parsed = self.parse_expression(expression, position);
+        self.state.exit();
+
+        let parsed = parsed?.map(|(end, children)| (end, SyntaxNode {
+            kind: SyntaxKind::Rule(name.clone()),
+            start: position,
+            end,
+            children,
+        }));
+
+        if self.grammar.packrat && !self.state.is_silenced() {
+            self.memo.insert((rule_index, position), parsed.clone());
+        }
+        Ok(parsed)
+    }
+
+    fn parse_expression(&mut self, expression: &Expression, position: usize) -> Result<Parsed, MatchError> {
+        match expression {
+            Expression::Token(regex) => {
+                self.state.set_groups(regex.groups());
+                Ok(regex.matches_prefix_at(&mut self.state, position)?
+                    .map(|end| (end, vec![SyntaxNode { kind: SyntaxKind::Token, start: position, end, children: vec![] }])))
+            }
+            Expression::Rule(name) => {
+                let rule_index = self.grammar.rule_index(name).ok_or_else(|| MatchError::UnknownRule(name.clone()))?;
+                Ok(self.parse_rule(rule_index, position)?.map(|(end, node)| (end, vec![node])))
+            }
+            Expression::Sequence(expressions) => {
+                let mut end = position;
+                let mut nodes = vec![];
+                for expression in expressions {
+                    match self.parse_expression(expression, end)? {
+                        Some((expression_end, expression_nodes)) => {
+                            end = expression_end;
+                            nodes.extend(expression_nodes);
+                        }
+                        None => return Ok(None)
+                    }
+                }
+                Ok(Some((end, nodes)))
+            }
+            Expression::Choice(expressions) => {
+                for expression in expressions {
+                    if let Some(parsed) = self.parse_expression(expression, position)? {
+                        return Ok(Some(parsed));
+                    }
+                }
+                Ok(None)
+            }
+            Expression::Repeat { expression, min, max } => {
+                let mut end = position;
+                let mut nodes = vec![];
+                let mut counter = 0;
+
+                while max.is_none_or(|max| counter < max) {
+                    match self.parse_expression(expression, end)? {
+                        // A repeat that consumed nothing would loop forever
+                        Some((expression_end, expression_nodes)) if expression_end > end || counter < *min => {
+                            end = expression_end;
+                            nodes.extend(expression_nodes);
+                            counter += 1;
+                        }
+                        _ => break
+                    }
+                }
+
+                match counter >= *min {
+                    true => Ok(Some((end, nodes))),
+                    false => Ok(None)
+                }
+            }
+            Expression::AndPredicate(expression) => Ok(self.parse_expression(expression, position)?
+                .map(|_| (position, vec![]))),
+            Expression::NotPredicate(expression) => {
+                self.state.silence();
+                let parsed = self.parse_expression(expression, position);
+                self.state.unsilence();
+
+                match parsed? {
+                    Some(_) => Ok(None),
+                    None => Ok(Some((position, vec![])))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/// What a SyntaxNode was created from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxKind {
+    /// The rule with the given name.
+    Rule(String),
+    /// A token, matched by a Regex.
+    Token,
+}
+
+/// A node of the concrete syntax tree created by parsing with a Grammar.
+/// Every rule and every token that was parsed gets a node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    /// The index of the first char of this node in the input.
+    pub start: usize,
+    /// The index behind the last char of this node in the input.
+    pub end: usize,
+    pub children: Vec<SyntaxNode>,
+}
+
+impl SyntaxNode {
+    /// Returns the part of the given input this node spans.
+    pub fn text(&self, input: &str) -> String {
+        input.chars().skip(self.start).take(self.end - self.start).collect()
+    }
+
+    /// Returns the name of the rule this node was created from, if it is not a token.
+    pub fn rule(&self) -> Option<&str> {
+        match &self.kind {
+            SyntaxKind::Rule(name) => Some(name),
+            SyntaxKind::Token => None
+        }
+    }
+
+    /// Returns all children created from the rule with the given name.
+    pub fn children_of_rule<'s>(&'s self, rule: &'s str) -> impl Iterator<Item=&'s SyntaxNode> + 's {
+        self.children.iter().filter(move |child| child.rule() == Some(rule))
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use readex::regex::Regex;
+
+const USAGE: &str = "Usage: readex [OPTIONS] PATTERN [PATH...]
+
+Searches the given files, or the standard input, for lines matching PATTERN.
+
+Options:
+  -n, --line-number      print the line number before each line
+  -c, --count            print only the number of matching lines
+  -v, --invert-match     select the lines that do not match
+  -C, --context NUM      print NUM lines before and after each matching line
+  -o, --only-matching    print only the matched parts of each line
+  -r, --recursive        search directories and everything inside them
+  -h, --help             print this help";
+
+/// What searching the lines of one input found.
+#[derive(Debug, PartialEq)]
+struct Searched {
+    /// How many lines were selected.
+    selected: usize,
+    /// The numbers of the lines matching failed on, with the error it failed with.
+    errors: Vec<(usize, String)>,
+}
+
+/// What the command line asked for.
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    pattern: String,
+    paths: Vec<PathBuf>,
+    line_numbers: bool,
+    count: bool,
+    invert: bool,
+    context: usize,
+    only_matching: bool,
+    recursive: bool,
+    help: bool,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("readex: {}\n\n{}", message, USAGE);
+            exit(2)
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let regex = match Regex::parse(&options.pattern) {
+        Ok(regex) => regex,
+        Err(error) => {
+            eprintln!("readex: invalid pattern {}", error);
+            exit(2)
+        }
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let mut selected = 0;
+    let mut failed = false;
+
+    if options.paths.is_empty() {
+        let stdin = std::io::stdin();
+        match search(&regex, &options, None, stdin.lock(), &mut out) {
+            Ok(searched) => {
+                selected += searched.selected;
+                failed |= report_errors("(standard input)", &searched.errors);
+            }
+            Err(message) => {
+                eprintln!("readex: (standard input): {}", message);
+                failed = true;
+            }
+        }
+    }
+
+    let show_names = options.paths.len() > 1 || options.recursive;
+    for path in &options.paths {
+        for file in files(path, options.recursive) {
+            let result = file.and_then(|file| {
+                let name = file.display().to_string();
+                let reader = BufReader::new(File::open(&file).map_err(|error| format!("{}: {}", name, error))?);
+                let shown_name = Some(name.as_str()).filter(|_| show_names);
+                let searched = search(&regex, &options, shown_name, reader, &mut out).map_err(|message| format!("{}: {}", name, message))?;
+                Ok((name, searched))
+            });
+            match result {
+                Ok((name, searched)) => {
+                    selected += searched.selected;
+                    failed |= report_errors(&name, &searched.errors);
+                }
+                Err(message) => {
+                    eprintln!("readex: {}", message);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    exit(match (failed, selected) {
+        (true, _) => 2,
+        (false, 0) => 1,
+        (false, _) => 0
+    })
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut positional: Vec<String> = vec![];
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            positional.extend(args.by_ref());
+            break;
+        }
+
+        let flags: Vec<String> = match arg.strip_prefix("--") {
+            Some(long) => vec![String::from(long)],
+            None if arg.starts_with('-') && arg.len() > 1 => arg.chars().skip(1).map(String::from).collect(),
+            None => {
+                positional.push(arg);
+                continue;
+            }
+        };
+
+        let mut flags = flags.into_iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "n" | "line-number" => options.line_numbers = true,
+                "c" | "count" => options.count = true,
+                "v" | "invert-match" => options.invert = true,
+                "o" | "only-matching" => options.only_matching = true,
+                "r" | "recursive" => options.recursive = true,
+                "h" | "help" => options.help = true,
+                "C" | "context" => {
+                    // The number may follow directly, like -C2, or as the next argument
+                    let attached: String = flags.by_ref().collect();
+                    let number = match attached.is_empty() {
+                        true => args.next().ok_or_else(|| format!("option {} needs a number", arg))?,
+                        false => attached
+                    };
+                    options.context = number.parse().map_err(|_| format!("invalid context length {}", number))?;
+                }
+                _ => return Err(format!("unknown option {}", arg))
+            }
+        }
+    }
+
+    if options.help {
+        return Ok(options);
+    }
+    let mut positional = positional.into_iter();
+    options.pattern = positional.next().ok_or_else(|| String::from("no pattern given"))?;
+    options.paths = positional.map(PathBuf::from).collect();
+    Ok(options)
+}
+
+/// Returns the files to search for the given path, which are all files inside it if it is
+/// a directory and the search is recursive.
+fn files(path: &Path, recursive: bool) -> Vec<Result<PathBuf, String>> {
+    if !path.is_dir() {
+        return vec![Ok(path.to_path_buf())];
+    }
+    if !recursive {
+        return vec![Err(format!("{}: is a directory", path.display()))];
+    }
+
+    let mut entries: Vec<PathBuf> = match std::fs::read_dir(path) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(error) => return vec![Err(format!("{}: {}", path.display(), error))]
+    };
+    entries.sort();
+    entries.iter().flat_map(|entry| files(entry, true)).collect()
+}
+
+/// Prints the given errors of lines of the given input and returns if there were any.
+fn report_errors(input: &str, errors: &[(usize, String)]) -> bool {
+    for (number, message) in errors {
+        eprintln!("readex: {}:{}: {}", input, number, message);
+    }
+    !errors.is_empty()
+}
+
+/// Searches the lines of the given reader and writes the selected ones, as the options say.
+/// Lines matching fails on are neither selected nor printed, but the search goes on.
+/// Only reading and writing errors stop it.
+fn search(regex: &Regex, options: &Options, name: Option<&str>, mut reader: impl BufRead, out: &mut impl Write) -> Result<Searched, String> {
+    let mut selected = 0;
+    let mut errors = vec![];
+    let mut before: VecDeque<(usize, String)> = VecDeque::new();
+    let mut after = 0;
+    let mut last_printed: Option<usize> = None;
+    let mut buffer = vec![];
+
+    for number in 1.. {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer).map_err(|error| error.to_string())? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buffer);
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        // Only printing the matched parts needs every match, otherwise the first one is enough
+        let matches = match options.only_matching && !options.invert {
+            true => regex.find_all(line),
+            false => regex.find(line).map(|found| found.into_iter().collect())
+        };
+        let matches = match matches {
+            Ok(matches) => matches,
+            Err(error) => {
+                errors.push((number, error.to_string()));
+                continue;
+            }
+        };
+        if matches.is_empty() != options.invert {
+            if options.context > 0 {
+                before.push_back((number, String::from(line)));
+                if before.len() > options.context {
+                    before.pop_front();
+                }
+            }
+            if after > 0 {
+                after -= 1;
+                if !options.count && !options.only_matching {
+                    print_line(out, options, name, number, '-', line)?;
+                    last_printed = Some(number);
+                }
+            }
+            continue;
+        }
+
+        selected += 1;
+        if options.count {
+            continue;
+        }
+
+        if options.only_matching {
+            if !options.invert {
+                let chars: Vec<char> = line.chars().collect();
+                for (start, end) in matches.into_iter().filter(|(start, end)| start < end) {
+                    print_line(out, options, name, number, ':', &chars[start..end].iter().collect::<String>())?;
+                }
+            }
+            continue;
+        }
+
+        if options.context > 0 {
+            let first = before.front().map_or(number, |(first, _)| *first);
+            if last_printed.is_some_and(|last| last + 1 < first) {
+                writeln!(out, "--").map_err(|error| error.to_string())?;
+            }
+            for (before_number, before_line) in before.drain(..).filter(|(before_number, _)| last_printed.is_none_or(|last| *before_number > last)) {
+                print_line(out, options, name, before_number, '-', &before_line)?;
+            }
+            after = options.context;
+        }
+        print_line(out, options, name, number, ':', line)?;
+        last_printed = Some(number);
+    }
+
+    if options.count {
+        let prefix = name.map(|name| format!("{}:", name)).unwrap_or_default();
+        writeln!(out, "{}{}", prefix, selected).map_err(|error| error.to_string())?;
+    }
+    Ok(Searched { selected, errors })
+}
+
+/// Writes a line with its name and number in front, as far as the options ask for them.
+/// The separator is `:` for selected lines and `-` for context lines.
+fn print_line(out: &mut impl Write, options: &Options, name: Option<&str>, number: usize, separator: char, line: &str) -> Result<(), String> {
+    let mut prefix = String::new();
+    if let Some(name) = name {
+        prefix.push_str(&format!("{}{}", name, separator));
+    }
+    if options.line_numbers {
+        prefix.push_str(&format!("{}{}", number, separator));
+    }
+    writeln!(out, "{}{}", prefix, line).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use readex::regex::Regex;
+
+    use crate::{Options, parse_args, search, Searched};
+
+    const TEXT: &str = "alpha 1\nbeta\ngamma 22\ndelta\nepsilon\nzeta 333\n";
+
+    fn run(pattern: &str, options: Options) -> String {
+        let regex = Regex::parse(pattern).unwrap();
+        let mut out = vec![];
+        search(&regex, &options, None, Cursor::new(TEXT), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn args(args: &str) -> Result<Options, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn success_parse_args() {
+        let options = args("-nv -C2 a+ one two").unwrap();
+
+        assert!(options.line_numbers && options.invert);
+        assert_eq!(2, options.context);
+        assert_eq!("a+", options.pattern);
+        assert_eq!(2, options.paths.len());
+        assert_eq!(3, args("--context 3 x").unwrap().context)
+    }
+
+    #[test]
+    fn success_search() {
+        assert_eq!("alpha 1\ngamma 22\nzeta 333\n", run(r"\d", Options::default()));
+        assert_eq!("2:beta\n4:delta\n", run(r"\d|^e", Options { invert: true, line_numbers: true, ..Options::default() }));
+        assert_eq!("3\n", run(r"\d", Options { count: true, ..Options::default() }))
+    }
+
+    #[test]
+    fn success_search_only_matching() {
+        assert_eq!("1\n22\n333\n", run(r"\d+", Options { only_matching: true, ..Options::default() }))
+    }
+
+    #[test]
+    fn success_search_with_context() {
+        let options = Options { context: 1, line_numbers: true, ..Options::default() };
+
+        assert_eq!("1:alpha 1\n2-beta\n--\n5-epsilon\n6:zeta 333\n", run("alpha|zeta", options));
+        assert_eq!("1:alpha 1\n2:beta\n3-gamma 22\n", run("^(alpha|beta)", Options { context: 1, line_numbers: true, ..Options::default() }))
+    }
+
+    #[test]
+    fn failure_search_goes_on_after_error() {
+        let regex = Regex::parse("(a|b)+").unwrap().with_recursion_limit(20);
+        let text = format!("ab\n{}\nba\n", "ab".repeat(20));
+        let mut out = vec![];
+
+        let searched = search(&regex, &Options { line_numbers: true, ..Options::default() }, None, Cursor::new(text), &mut out).unwrap();
+        assert_eq!(Searched { selected: 2, errors: vec![(2, String::from("Matching exceeded the recursion limit of 20!"))] }, searched);
+        assert_eq!("1:ab\n3:ba\n", String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn failure_parse_args() {
+        assert_eq!(Err(String::from("no pattern given")), args("-n"));
+        assert_eq!(Err(String::from("unknown option -x")), args("-x foo"));
+        assert!(args("-C foo").is_err())
+    }
+}
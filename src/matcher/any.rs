@@ -11,6 +11,14 @@ impl Matcher for Any {
     fn checked_string_length(&self) -> usize {
         1
     }
+
+    fn description(&self) -> String {
+        String::from("any char")
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        Some(vec![('\0', char::MAX)])
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,175 @@
+use crate::matcher::Matcher;
+
+/// Matcher that matches a single char out of a set of char ranges.
+pub struct Class {
+    ranges: Vec<(char, char)>
+}
+
+impl Class {
+    /// Creates a class from inclusive char ranges. Overlapping ranges are merged.
+    pub fn new(ranges: &[(char, char)]) -> Self {
+        Class {
+            ranges: normalize(ranges.to_vec())
+        }
+    }
+
+    /// Creates a class that matches every char of the given string.
+    pub fn of(chars: &str) -> Self {
+        let ranges: Vec<(char, char)> = chars.chars().map(|c| (c, c)).collect();
+        Class::new(&ranges)
+    }
+
+    /// Creates a class that matches the ASCII digits.
+    pub fn digit() -> Self {
+        Class::new(&[('0', '9')])
+    }
+
+    /// Creates a class that matches ASCII letters, digits and the underscore.
+    pub fn word() -> Self {
+        Class::new(&[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')])
+    }
+
+    /// Creates a class that matches ASCII whitespace.
+    pub fn whitespace() -> Self {
+        Class::new(&[('\t', '\r'), (' ', ' ')])
+    }
+
+    /// Creates a class that matches every char this one does not match.
+    pub fn complement(&self) -> Self {
+        let mut ranges = vec![];
+        let mut start = Some('\0');
+        for (range_start, range_end) in &self.ranges {
+            if let Some(start) = start.filter(|start| start < range_start) {
+                ranges.push((start, previous_char(*range_start)));
+            }
+            start = next_char(*range_end);
+        }
+        if let Some(start) = start {
+            ranges.push((start, char::MAX));
+        }
+        Class { ranges }
+    }
+
+    pub fn ranges(&self) -> &[(char, char)] {
+        &self.ranges
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges.iter().any(|(start, end)| *start <= c && c <= *end)
+    }
+}
+
+impl Matcher for Class {
+    fn matches(&self, string: String) -> bool {
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => self.contains(c),
+            _ => false
+        }
+    }
+
+    fn checked_string_length(&self) -> usize {
+        1
+    }
+
+    fn description(&self) -> String {
+        match self.ranges.as_slice() {
+            [('0', '9')] => String::from("a digit"),
+            [(start, end)] if start == end => format!("`{}`", start),
+            ranges => {
+                let ranges: String = ranges.iter()
+                    .map(|(start, end)| match start == end {
+                        true => start.to_string(),
+                        false => format!("{}-{}", start, end)
+                    })
+                    .collect();
+                format!("one of [{}]", ranges)
+            }
+        }
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        Some(self.ranges.clone())
+    }
+}
+
+/// Sorts the given ranges and merges the ones that overlap or touch.
+pub(crate) fn normalize(mut ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+    ranges.retain(|(start, end)| start <= end);
+    ranges.sort();
+
+    let mut normalized: Vec<(char, char)> = vec![];
+    for (start, end) in ranges {
+        match normalized.last_mut() {
+            Some((_, last_end)) if next_char(*last_end).is_none_or(|next| start <= next) => *last_end = (*last_end).max(end),
+            _ => normalized.push((start, end))
+        }
+    }
+    normalized
+}
+
+/// Returns the char following the given one, skipping the surrogate range.
+pub(crate) fn next_char(c: char) -> Option<char> {
+    match c {
+        '\u{D7FF}' => Some('\u{E000}'),
+        char::MAX => None,
+        c => char::from_u32(c as u32 + 1)
+    }
+}
+
+/// Returns the char before the given one, skipping the surrogate range.
+pub(crate) fn previous_char(c: char) -> char {
+    match c {
+        '\u{E000}' => '\u{D7FF}',
+        c => char::from_u32(c as u32 - 1).unwrap_or(c)
+    }
+}
+
+/// Returns how many chars the given inclusive range contains, without the surrogate range.
+pub(crate) fn range_size(start: char, end: char) -> u32 {
+    if start > end {
+        return 0;
+    }
+    let surrogates = match start <= '\u{D7FF}' && end >= '\u{E000}' {
+        true => 0x800,
+        false => 0
+    };
+    end as u32 - start as u32 + 1 - surrogates
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::class::Class;
+    use crate::matcher::Matcher;
+
+    #[test]
+    fn success_matches() {
+        let matcher = Class::new(&[('a', 'c'), ('x', 'x')]);
+
+        assert!(matcher.matches(String::from("b")));
+        assert!(matcher.matches(String::from("x")));
+        assert!(!matcher.matches(String::from("d")))
+    }
+
+    #[test]
+    fn success_ranges_are_merged() {
+        let matcher = Class::new(&[('d', 'f'), ('a', 'c'), ('x', 'x'), ('b', 'd')]);
+
+        assert_eq!(&[('a', 'f'), ('x', 'x')], matcher.ranges());
+        assert_eq!("one of [a-fx]", matcher.description())
+    }
+
+    #[test]
+    fn success_complement() {
+        let class = Class::new(&[('0', '9'), ('\u{E000}', char::MAX)]).complement();
+
+        assert_eq!(&[('\0', '/'), (':', '\u{D7FF}')], class.ranges());
+        assert_eq!(&[('\0', char::MAX)], Class::new(&[]).complement().ranges())
+    }
+
+    #[test]
+    fn success_digit() {
+        assert_eq!("a digit", Class::digit().description());
+        assert!(Class::digit().matches(String::from("7")))
+    }
+}
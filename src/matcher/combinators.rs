@@ -0,0 +1,255 @@
+use std::fmt::Formatter;
+
+use crate::matcher::class::Class;
+use crate::matcher::Matcher;
+
+/// Matcher that matches what either of its matchers matches. A matcher checks a fixed
+/// number of chars, so both have to check the same number.
+pub struct OrMatcher<A: Matcher, B: Matcher> {
+    first: A,
+    second: B,
+}
+
+impl<A: Matcher, B: Matcher> OrMatcher<A, B> {
+    /// Fails if the given matchers check a different number of chars.
+    pub fn new(first: A, second: B) -> Result<Self, CombineError> {
+        check_lengths(&first, &second)?;
+        Ok(OrMatcher {
+            first,
+            second
+        })
+    }
+}
+
+impl<A: Matcher, B: Matcher> Matcher for OrMatcher<A, B> {
+    fn matches(&self, string: String) -> bool {
+        self.first.matches(string.clone()) || self.second.matches(string)
+    }
+
+    fn checked_string_length(&self) -> usize {
+        self.first.checked_string_length()
+    }
+
+    fn description(&self) -> String {
+        format!("{} or {}", self.first.description(), self.second.description())
+    }
+
+    fn could_start_with(&self, prefix: &str) -> bool {
+        self.first.could_start_with(prefix) || self.second.could_start_with(prefix)
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        let mut chars = self.first.chars()?;
+        chars.extend(self.second.chars()?);
+        Some(Class::new(&chars).ranges().to_vec())
+    }
+}
+
+/// Matcher that matches what both of its matchers match. A matcher checks a fixed
+/// number of chars, so both have to check the same number.
+pub struct AndMatcher<A: Matcher, B: Matcher> {
+    first: A,
+    second: B,
+}
+
+impl<A: Matcher, B: Matcher> AndMatcher<A, B> {
+    /// Fails if the given matchers check a different number of chars.
+    pub fn new(first: A, second: B) -> Result<Self, CombineError> {
+        check_lengths(&first, &second)?;
+        Ok(AndMatcher {
+            first,
+            second
+        })
+    }
+}
+
+impl<A: Matcher, B: Matcher> Matcher for AndMatcher<A, B> {
+    fn matches(&self, string: String) -> bool {
+        self.first.matches(string.clone()) && self.second.matches(string)
+    }
+
+    fn checked_string_length(&self) -> usize {
+        self.first.checked_string_length()
+    }
+
+    fn description(&self) -> String {
+        format!("{} and {}", self.first.description(), self.second.description())
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        let first = Class::new(&self.first.chars()?).complement();
+        let second = Class::new(&self.second.chars()?).complement();
+        let mut either = first.ranges().to_vec();
+        either.extend_from_slice(second.ranges());
+        Some(Class::new(&either).complement().ranges().to_vec())
+    }
+}
+
+/// Matcher that matches every string of the checked length its matcher does not match.
+pub struct NotMatcher<M: Matcher> {
+    inner: M
+}
+
+impl<M: Matcher> NotMatcher<M> {
+    pub fn new(inner: M) -> Self {
+        NotMatcher {
+            inner
+        }
+    }
+}
+
+impl<M: Matcher> Matcher for NotMatcher<M> {
+    fn matches(&self, string: String) -> bool {
+        !self.inner.matches(string)
+    }
+
+    fn checked_string_length(&self) -> usize {
+        self.inner.checked_string_length()
+    }
+
+    fn description(&self) -> String {
+        format!("not {}", self.inner.description())
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        Some(Class::new(&self.inner.chars()?).complement().ranges().to_vec())
+    }
+}
+
+/// Matcher that matches its matcher the given number of times in a row.
+pub struct RepeatMatcher<M: Matcher> {
+    inner: M,
+    times: usize,
+}
+
+impl<M: Matcher> RepeatMatcher<M> {
+    pub fn new(inner: M, times: usize) -> Self {
+        RepeatMatcher {
+            inner,
+            times
+        }
+    }
+}
+
+impl<M: Matcher> Matcher for RepeatMatcher<M> {
+    fn matches(&self, string: String) -> bool {
+        let chars: Vec<char> = string.chars().collect();
+        let length = self.inner.checked_string_length();
+        if chars.len() != length * self.times {
+            return false;
+        }
+
+        match length {
+            0 => self.times == 0 || self.inner.matches(string),
+            _ => chars.chunks(length).all(|chunk| self.inner.matches(chunk.iter().collect()))
+        }
+    }
+
+    fn checked_string_length(&self) -> usize {
+        self.inner.checked_string_length() * self.times
+    }
+
+    fn description(&self) -> String {
+        format!("{} times {}", self.times, self.inner.description())
+    }
+
+    fn literal(&self) -> Option<String> {
+        Some(self.inner.literal()?.repeat(self.times))
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        match self.times {
+            1 => self.inner.chars(),
+            _ => None
+        }
+    }
+}
+
+/// Fails if the given matchers check a different number of chars.
+fn check_lengths(first: &impl Matcher, second: &impl Matcher) -> Result<(), CombineError> {
+    let lengths = (first.checked_string_length(), second.checked_string_length());
+    match lengths.0 == lengths.1 {
+        true => Ok(()),
+        false => Err(CombineError::DifferentLengths(lengths.0, lengths.1))
+    }
+}
+
+/// Everything that keeps matchers from being combined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombineError {
+    /// The first matcher checks the first number of chars, the second one the second number.
+    DifferentLengths(usize, usize),
+}
+
+impl std::error::Error for CombineError {}
+
+impl std::fmt::Display for CombineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DifferentLengths(first, second) => write!(f, "Matchers checking {} and {} chars cannot be combined!", first, second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::class::Class;
+    use crate::matcher::combinators::CombineError;
+    use crate::matcher::fn_matcher::FnMatcher;
+    use crate::matcher::Matcher;
+    use crate::matcher::string::Str;
+    use crate::regex::Regex;
+
+    #[test]
+    fn success_or_and_not() {
+        let vowel = Class::of("aeiou");
+        let consonant = FnMatcher::new(|c: char| c.is_ascii_lowercase()).and(vowel.not()).unwrap();
+        let letter_or_digit = consonant.or(Class::digit()).unwrap();
+
+        assert!(letter_or_digit.matches(String::from("b")));
+        assert!(letter_or_digit.matches(String::from("7")));
+        assert!(!letter_or_digit.matches(String::from("e")));
+        assert_eq!("a custom matcher and not one of [aeiou] or a digit", letter_or_digit.description())
+    }
+
+    #[test]
+    fn success_chars_of_combinations() {
+        let matcher = Class::new(&[('a', 'z')]).and(Class::of("m").not()).unwrap().or(Str::new("0")).unwrap();
+
+        assert_eq!(Some(vec![('0', '0'), ('a', 'l'), ('n', 'z')]), matcher.chars());
+        assert!(Regex::matcher(matcher).is_equivalent(&Regex::parse("[a-ln-z0]").unwrap()).unwrap().holds())
+    }
+
+    #[test]
+    fn success_repeat() {
+        let matcher = Class::digit().repeat(3);
+
+        assert_eq!(3, matcher.checked_string_length());
+        assert!(matcher.matches(String::from("123")));
+        assert!(!matcher.matches(String::from("1a3")));
+        assert_eq!(Some(String::from("abab")), Str::new("ab").repeat(2).literal())
+    }
+
+    #[test]
+    fn success_or_of_longer_matchers() {
+        let matcher = Str::new("ab").or(Str::new("cd")).unwrap();
+
+        assert!(matcher.matches(String::from("ab")));
+        assert!(matcher.matches(String::from("cd")));
+        assert!(matcher.could_start_with("c"));
+        assert!(!matcher.could_start_with("b"))
+    }
+
+    #[test]
+    fn failure_and_of_different_lengths() {
+        assert_eq!(Some(CombineError::DifferentLengths(2, 1)), Str::new("ab").and(FnMatcher::new(|_| true)).err())
+    }
+
+    #[test]
+    fn failure_or_of_different_lengths() {
+        let error = Str::new("a").or(Str::new("bc")).err().unwrap();
+
+        assert_eq!(CombineError::DifferentLengths(1, 2), error);
+        assert_eq!("Matchers checking 1 and 2 chars cannot be combined!", error.to_string())
+    }
+}
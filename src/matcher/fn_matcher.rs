@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use crate::matcher::Matcher;
+
+/// Matcher that matches a single char for which the given function returns true.
+/// Over other tokens than chars, it is no Matcher, but can be turned into a regex with
+/// Regex::token_matcher.
+///
+/// Examples:
+/// ```
+/// use crate::readex::regex::Regex;
+/// use crate::readex::matcher::fn_matcher::FnMatcher;
+/// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+///
+/// let hex = FnMatcher::new(|c: char| c.is_ascii_hexdigit()).described_as("a hex digit");
+/// let regex = Regex::from("0x") + Regex::matcher(hex).that_repeats(ZeroToInfinity);
+///
+/// assert!(regex.matches("0x1F"));
+/// assert_eq!("at column 3: expected a hex digit or the end of the string, found `g`", regex.diagnose("0xg").unwrap().unwrap().to_string());
+/// ```
+pub struct FnMatcher<F: Fn(T) -> bool, T = char> {
+    function: F,
+    description: Option<String>,
+    tokens: PhantomData<fn(T)>,
+}
+
+impl<F: Fn(T) -> bool, T> FnMatcher<F, T> {
+    pub fn new(function: F) -> Self {
+        FnMatcher {
+            function,
+            description: None,
+            tokens: PhantomData
+        }
+    }
+
+    /// Returns if the function accepts the given token.
+    pub fn accepts(&self, token: T) -> bool {
+        (self.function)(token)
+    }
+
+    /// Sets what this matcher expects, for messages about failed matches.
+    pub fn described_as(mut self, description: &str) -> Self {
+        self.description = Some(String::from(description));
+        self
+    }
+}
+
+impl<F: Fn(char) -> bool> Matcher for FnMatcher<F> {
+    fn matches(&self, string: String) -> bool {
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => self.accepts(c),
+            _ => false
+        }
+    }
+
+    fn checked_string_length(&self) -> usize {
+        1
+    }
+
+    fn description(&self) -> String {
+        self.description.clone().unwrap_or_else(|| String::from("a custom matcher"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::fn_matcher::FnMatcher;
+    use crate::matcher::Matcher;
+    use crate::regex::Regex;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_matches() {
+        let matcher = FnMatcher::new(|c: char| c.is_uppercase());
+
+        assert!(matcher.matches(String::from("Ä")));
+        assert!(!matcher.matches(String::from("a")));
+        assert!(!matcher.matches(String::from("AB")));
+        assert_eq!("a custom matcher", matcher.description())
+    }
+
+    #[test]
+    fn success_matches_tokens() {
+        let even = FnMatcher::new(|n: u32| n.is_multiple_of(2));
+        let regex = Regex::token_matcher(even).that_repeats(ZeroToInfinity);
+
+        assert!(regex.matches_tokens(&[2, 4, 6]));
+        assert!(!regex.matches_tokens(&[2, 3]))
+    }
+}
@@ -1,10 +1,62 @@
-use crate::string_pointer::StringPointer;
+use crate::matcher::combinators::{AndMatcher, CombineError, NotMatcher, OrMatcher, RepeatMatcher};
 
 pub mod string;
 pub mod any;
+pub mod class;
+pub mod fn_matcher;
+pub mod combinators;
 
 pub trait Matcher {
     fn matches(&self, string: String) -> bool;
 
     fn checked_string_length(&self) -> usize;
-}
\ No newline at end of file
+
+    /// Describes what this matcher expects, used in messages about failed matches.
+    fn description(&self) -> String {
+        String::from("a custom matcher")
+    }
+
+    /// Returns the one string this matcher matches, if there is only one.
+    fn literal(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the chars this matcher matches as inclusive ranges,
+    /// if it checks exactly one char and the chars are known.
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        None
+    }
+
+    /// Returns if a string this matcher matches could begin with the given prefix,
+    /// which is shorter than the checked string length. Used to tell if more input
+    /// could still lead to a match. Without a known literal or chars, any prefix could.
+    fn could_start_with(&self, prefix: &str) -> bool {
+        match (self.literal(), self.chars()) {
+            (Some(literal), _) => literal.starts_with(prefix),
+            (None, Some(chars)) => !chars.is_empty(),
+            (None, None) => true
+        }
+    }
+
+    /// Combines this matcher with another one, so that either of them has to match.
+    /// Fails if the other one checks a different number of chars.
+    fn or<M: Matcher>(self, other: M) -> Result<OrMatcher<Self, M>, CombineError> where Self: Sized {
+        OrMatcher::new(self, other)
+    }
+
+    /// Combines this matcher with another one, so that both of them have to match.
+    /// Fails if the other one checks a different number of chars.
+    fn and<M: Matcher>(self, other: M) -> Result<AndMatcher<Self, M>, CombineError> where Self: Sized {
+        AndMatcher::new(self, other)
+    }
+
+    /// Turns this matcher into one that matches what this one does not.
+    fn not(self) -> NotMatcher<Self> where Self: Sized {
+        NotMatcher::new(self)
+    }
+
+    /// Turns this matcher into one that matches this one the given number of times in a row.
+    fn repeat(self, times: usize) -> RepeatMatcher<Self> where Self: Sized {
+        RepeatMatcher::new(self, times)
+    }
+}
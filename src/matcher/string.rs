@@ -1,5 +1,4 @@
 use crate::matcher::Matcher;
-use crate::string_pointer::StringPointer;
 
 /// Matcher that matches a given, exact String.
 pub struct Str {
@@ -15,13 +14,28 @@ impl Str {
 }
 
 impl Matcher for Str {
-    fn matches(&self, mut string: String) -> bool {
-        println!("{}", format!("Matching: {}", string));
+    fn matches(&self, string: String) -> bool {
         string == self.string
     }
 
     fn checked_string_length(&self) -> usize {
-        self.string.len()
+        self.string.chars().count()
+    }
+
+    fn description(&self) -> String {
+        format!("`{}`", self.string)
+    }
+
+    fn literal(&self) -> Option<String> {
+        Some(self.string.clone())
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        let mut chars = self.string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(vec![(c, c)]),
+            _ => None
+        }
     }
 }
 
@@ -29,7 +43,6 @@ impl Matcher for Str {
 mod tests {
     use crate::matcher::Matcher;
     use crate::matcher::string::Str;
-    use crate::string_pointer::StringPointer;
 
     #[test]
     pub fn success_matches() {
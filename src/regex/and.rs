@@ -1,22 +1,70 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::{AutomatonError, DEFAULT_STATE_LIMIT};
+use crate::automaton::nfa::{Fragment, Nfa};
 use crate::regex::Regex;
-use crate::regex::regex_type::RegexType;
-use crate::string_pointer::{StringPointer, StringPointerError};
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
 
-pub struct And<'a> {
-    left: Regex<'a>,
-    right: Regex<'a>,
+/// Matches a part of the string if both the left and the right regex match exactly this part.
+pub struct And<'a, T = char> {
+    left: Regex<'a, T>,
+    right: Regex<'a, T>,
 }
 
-impl<'a> RegexType for And<'a> {
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
-        Ok(false)
+impl<'a, T> RegexType<'a, T> for And<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let start = state.checkpoint();
+        self.left.matches_string(state, &mut |state| {
+            let end = state.cursor.index();
+            state.backtrack(start);
+            // Only the left regex reports what it expected
+            let right_matches = state.silenced(|state| self.right.matches_string(state, &mut |state| Ok(state.cursor.index() == end)))?;
+
+            state.cursor.set_index(end);
+            match right_matches {
+                true => next(state),
+                false => Ok(false)
+            }
+        })
+    }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        self.left.nullable(nullable_rules) && self.right.nullable(nullable_rules)
+    }
+
+    fn literals(&self) -> Literals {
+        self.left.literals().both(self.right.literals())
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        let left = self.left.to_dfa_over(alphabet)?;
+        let right = self.right.to_dfa_over(alphabet)?;
+        Ok(nfa.embed(&left.intersection(&right, DEFAULT_STATE_LIMIT)?))
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        output.push_str(&generator.generate_matching(&self.left, &self.right)?);
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        Regex::and(self.left.simplified(stats), self.right.simplified(stats))
     }
 }
 
-impl<'a> And<'a> {
-    pub fn new(left: Regex<'a>, right: Regex<'a>) -> Self {
+impl<'a, T> And<'a, T> {
+    pub fn new(left: Regex<'a, T>, right: Regex<'a, T>) -> Self {
         And {
             left,
             right,
@@ -24,8 +72,34 @@ impl<'a> And<'a> {
     }
 }
 
-impl<'a> Display for And<'a> {
+impl<'a, T> Display for And<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "And")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::matcher::string::Str;
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+
+    #[test]
+    fn success_matches() {
+        let regex = Regex::and(Regex::matcher(Any).that_repeats(Times::new(3)), Regex::matcher(Str::new("foo")));
+
+        assert!(regex.matches("foo"));
+        assert!(!regex.matches("bar"));
+        assert!(!regex.matches("fooo"))
+    }
+
+    #[test]
+    fn success_diagnose_ignores_right() {
+        let regex = Regex::and(Regex::from("ab"), Regex::from("a") + "c") | "x";
+
+        let diagnosis = regex.diagnose("ab").unwrap().unwrap();
+        assert_eq!(0, diagnosis.position);
+        assert_eq!(vec![String::from("`x`")], diagnosis.expected)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::regex::Regex;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+
+/// What each kind of edit costs when matching approximately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditCosts {
+    /// The cost of a char in the text that the regex does not expect.
+    pub insertion: usize,
+    /// The cost of a char the regex expects, but the text lacks.
+    pub deletion: usize,
+    /// The cost of a char in the text that differs from the one the regex expects.
+    pub substitution: usize,
+}
+
+impl EditCosts {
+    pub fn new(insertion: usize, deletion: usize, substitution: usize) -> Self {
+        EditCosts {
+            insertion,
+            deletion,
+            substitution
+        }
+    }
+}
+
+impl Default for EditCosts {
+    /// Every edit costs 1, which makes the cost of a match its Levenshtein distance.
+    fn default() -> Self {
+        EditCosts::new(1, 1, 1)
+    }
+}
+
+/// A part of a text that a Regex matches after some edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApproximateMatch {
+    /// The char index the match starts at.
+    pub start: usize,
+    /// The char index after the last char of the match.
+    pub end: usize,
+    /// What the cheapest edits that make the part match cost.
+    pub cost: usize,
+}
+
+impl<'a> Regex<'a> {
+    /// Searches the given text for all parts this Regex matches after at most the given
+    /// number of inserted, deleted or substituted chars, from left to right.
+    /// See find_approximate_with for how the matches are chosen.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::approximate::ApproximateMatch;
+    ///
+    /// let regex = Regex::parse("colou?r").unwrap();
+    ///
+    /// assert_eq!(vec![
+    ///     ApproximateMatch { start: 0, end: 5, cost: 0 },
+    ///     ApproximateMatch { start: 6, end: 11, cost: 1 },
+    /// ], regex.find_approximate("color co1or", 1).unwrap());
+    /// ```
+    pub fn find_approximate(&self, text: &str, max_edits: usize) -> Result<Vec<ApproximateMatch>, MatchError> {
+        self.find_approximate_with(text, max_edits, EditCosts::default())
+    }
+
+    /// Searches the given text for all parts this Regex matches after edits that cost
+    /// at most the given maximum, from left to right. The parts do not overlap.
+    /// At each position, the cheapest match is taken, and of equally cheap ones the first
+    /// that find would take. A match never starts with an inserted char, and it only consumes
+    /// nothing if the regex matches nothing anyway.
+    ///
+    /// Every matcher can be edited. Literals are edited char by char, other matchers as a whole,
+    /// which costs as much as editing each of their chars. Backreferences, lookarounds and
+    /// the inner regex of a Not only match exactly.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::approximate::{ApproximateMatch, EditCosts};
+    ///
+    /// let regex = Regex::parse(r"item \d\d").unwrap();
+    /// let costs = EditCosts::new(2, 2, 1);
+    ///
+    /// assert_eq!(vec![ApproximateMatch { start: 0, end: 7, cost: 1 }], regex.find_approximate_with("itam 42", 2, costs).unwrap());
+    /// assert_eq!(vec![ApproximateMatch { start: 0, end: 6, cost: 2 }], regex.find_approximate_with("item 4", 2, costs).unwrap());
+    /// ```
+    pub fn find_approximate_with(&self, text: &str, max_cost: usize, costs: EditCosts) -> Result<Vec<ApproximateMatch>, MatchError> {
+        let mut state = MatchState::new(text, self.recursion_limit).with_groups_of(self).approximating(costs, max_cost);
+        let mut matches = vec![];
+        let mut position = 0;
+        for start in 0..=text.chars().count() {
+            if start < position {
+                continue;
+            }
+            if let Some(found) = self.cheapest_match_at(&mut state, start, max_cost)? {
+                position = found.end.max(start + 1);
+                matches.push(found);
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Returns the cheapest approximate match beginning at the given position, if any costs at most the given maximum.
+    /// The groups of this Regex must already be set on the MatchState.
+    fn cheapest_match_at(&self, state: &mut MatchState, start: usize, max_cost: usize) -> Result<Option<ApproximateMatch>, MatchError> {
+        state.clear_captures();
+        state.cursor.set_index(start);
+        state.approximate_from(start, max_cost);
+
+        let mut cheapest: Option<ApproximateMatch> = None;
+        self.matches_string(state, &mut |state| {
+            let end = state.cursor.index();
+            let cost = state.edit_cost();
+            if end == start && cost > 0 {
+                return Ok(false);
+            }
+
+            cheapest = Some(ApproximateMatch { start, end, cost });
+            match cost {
+                0 => Ok(true),
+                // Only cheaper matches are of interest from now on
+                _ => {
+                    state.limit_edit_cost(cost - 1);
+                    Ok(false)
+                }
+            }
+        })?;
+        Ok(cheapest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::class::Class;
+    use crate::regex::approximate::{ApproximateMatch, EditCosts};
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+
+    fn found(start: usize, end: usize, cost: usize) -> ApproximateMatch {
+        ApproximateMatch { start, end, cost }
+    }
+
+    #[test]
+    fn success_find_approximate_edits() {
+        let regex = Regex::from("hello");
+
+        assert_eq!(vec![found(0, 5, 1)], regex.find_approximate("hallo", 1).unwrap());
+        assert_eq!(vec![found(0, 4, 1)], regex.find_approximate("helo", 1).unwrap());
+        assert_eq!(vec![found(0, 6, 1)], regex.find_approximate("hel-lo", 1).unwrap());
+        assert_eq!(Vec::<ApproximateMatch>::new(), regex.find_approximate("hxllx", 1).unwrap())
+    }
+
+    #[test]
+    fn success_find_approximate_prefers_exact() {
+        let regex = Regex::from("cat") | "cart";
+
+        assert_eq!(vec![found(0, 4, 0)], regex.find_approximate("cart", 1).unwrap());
+        assert_eq!(vec![found(1, 4, 0)], regex.find_approximate("xcat", 1).unwrap())
+    }
+
+    #[test]
+    fn success_find_approximate_in_repeats() {
+        let regex = Regex::from("id=") + Regex::matcher(Class::digit()).that_repeats(Times::new(3));
+
+        assert_eq!(vec![found(0, 6, 1)], regex.find_approximate("id=1O3", 1).unwrap());
+        assert_eq!(vec![found(0, 6, 1)], regex.find_approximate("id:123", 1).unwrap())
+    }
+
+    #[test]
+    fn success_find_approximate_with_costs() {
+        let regex = Regex::from("abc");
+        let costs = EditCosts::new(1, 5, 5);
+
+        assert_eq!(vec![found(0, 4, 1)], regex.find_approximate_with("abxc", 2, costs).unwrap());
+        assert_eq!(Vec::<ApproximateMatch>::new(), regex.find_approximate_with("ac", 2, costs).unwrap())
+    }
+
+    #[test]
+    fn failure_find_approximate_lookaround_is_exact() {
+        let regex = Regex::from("a") + Regex::lookahead(Regex::from("b"));
+
+        assert_eq!(Vec::<ApproximateMatch>::new(), regex.find_approximate("ac", 1).unwrap());
+        assert_eq!(vec![found(0, 1, 0)], regex.find_approximate("ab", 1).unwrap())
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+
+use crate::regex::completion::Completion;
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::Regex;
+use crate::regex::simplify::SimplifyStats;
+
+/// Refers to a group either by its number or by its name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupReference {
+    Number(usize),
+    Name(String),
+}
+
+impl Display for GroupReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupReference::Number(number) => write!(f, "{}", number),
+            GroupReference::Name(name) => write!(f, "<{}>", name)
+        }
+    }
+}
+
+/// Matches exactly the text, or the tokens, the referenced group captured before.
+/// Does not match if the group did not capture anything yet.
+pub struct Backreference {
+    group: GroupReference
+}
+
+impl<'a, T: 'a> RegexType<'a, T> for Backreference {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let start = state.cursor.index();
+        let (captured_start, captured_end) = match state.captured_span(&self.group)? {
+            Some(span) => span,
+            None => {
+                state.expect(start, || format!("the text of group {}", self.group));
+                return Ok(false);
+            }
+        };
+
+        match state.cursor.repeats(captured_start, captured_end) {
+            true => next(state),
+            false => {
+                let captured = state.cursor.text(captured_start, captured_end);
+                let rest = state.cursor.text(start, state.cursor.len());
+                let could_follow = match (&captured, &rest) {
+                    (Some(captured), Some(rest)) => captured.starts_with(rest.as_str()),
+                    _ => true
+                };
+                if could_follow && state.cursor.len() - start < captured_end - captured_start {
+                    state.expect_more_input(|| {
+                        let remainder = captured.as_deref()?.chars().skip(rest?.chars().count()).collect();
+                        Some(Completion::Literal(remainder))
+                    });
+                }
+                state.expect(start, || match captured {
+                    Some(captured) => format!("`{}` from group {}", captured, self.group),
+                    None => format!("the tokens of group {}", self.group)
+                });
+                Ok(false)
+            }
+        }
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        let captured = generator.captured_text(&self.group).ok_or(GenerateError::NoStringFound(1))?;
+        output.push_str(&captured);
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, _stats: &mut SimplifyStats) -> Regex<'a, T> {
+        Regex::new_regex(*self)
+    }
+
+    fn single_end(&self) -> bool {
+        true
+    }
+}
+
+impl Backreference {
+    pub fn new(group: GroupReference) -> Self {
+        Backreference {
+            group
+        }
+    }
+}
+
+impl Display for Backreference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Backreference {}", self.group)
+    }
+}
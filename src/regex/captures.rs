@@ -0,0 +1,44 @@
+/// The parts of a string captured by the groups of a Regex.
+/// Group 0 is the whole match.
+#[derive(Debug, PartialEq)]
+pub struct Captures {
+    string: String,
+    spans: Vec<Option<(usize, usize)>>,
+    names: Vec<Option<String>>,
+}
+
+impl Captures {
+    pub(crate) fn new(string: &str, spans: Vec<Option<(usize, usize)>>, names: Vec<Option<String>>) -> Self {
+        Captures {
+            string: String::from(string),
+            spans,
+            names,
+        }
+    }
+
+    /// Returns the text captured by the group with the given number.
+    pub fn get(&self, number: usize) -> Option<String> {
+        let (start, end) = self.span(number)?;
+        Some(self.string.chars().skip(start).take(end - start).collect())
+    }
+
+    /// Returns the text captured by the group with the given name.
+    pub fn name(&self, name: &str) -> Option<String> {
+        let number = self.names.iter().position(|group_name| group_name.as_deref() == Some(name))?;
+        self.get(number)
+    }
+
+    /// Returns the start and end char index of the text captured by the group with the given number.
+    pub fn span(&self, number: usize) -> Option<(usize, usize)> {
+        self.spans.get(number).copied().flatten()
+    }
+
+    /// Returns the number of groups, including group 0.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
@@ -0,0 +1,238 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::regex::Regex;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+
+/// How many candidates shortest_completion tries by default, before giving up.
+pub const DEFAULT_COMPLETION_LIMIT: usize = 1_000;
+
+/// Something that can be appended to a partial input to get closer to a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Completion {
+    /// The rest of a literal the input stopped in, like `ar` for `Str::new("bar")` after `b`.
+    Literal(String),
+    /// Any one char out of these inclusive ranges, like the chars of a Class.
+    Chars(Vec<(char, char)>),
+}
+
+impl Completion {
+    /// Returns a string this completion stands for, preferring printable chars.
+    fn example(&self) -> String {
+        match self {
+            Completion::Literal(literal) => literal.clone(),
+            Completion::Chars(ranges) => {
+                let printable = ranges.iter()
+                    .find(|(start, end)| *start <= '~' && *end >= ' ')
+                    .map(|(start, _)| (*start).max(' '));
+                printable.or_else(|| ranges.first().map(|(start, _)| *start))
+                    .map(String::from)
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A string shortest_completion tries, together with the chars in it that were picked
+/// out of a Completion::Chars. Candidates are ordered by their length first.
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord)]
+struct Candidate {
+    length: usize,
+    text: String,
+    picks: Vec<Pick>,
+}
+
+/// A char of a Candidate picked out of the given ranges, at the given byte index.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Pick {
+    index: usize,
+    ranges: Vec<(char, char)>,
+}
+
+impl Candidate {
+    fn new(text: String, picks: Vec<Pick>) -> Self {
+        Candidate {
+            length: text.chars().count(),
+            text,
+            picks
+        }
+    }
+
+    /// Returns this candidate with an example of the given completion appended.
+    fn extended(&self, completion: &Completion) -> Candidate {
+        let mut picks = self.picks.clone();
+        if let Completion::Chars(ranges) = completion {
+            picks.push(Pick { index: self.text.len(), ranges: ranges.clone() });
+        }
+        Candidate::new(format!("{}{}", self.text, completion.example()), picks)
+    }
+
+    /// Returns the candidate that picks the next char out of the ranges of the last pick
+    /// that has one, instead of the char picked so far. Everything after it is dropped.
+    fn other_pick(&self) -> Option<Candidate> {
+        self.picks.iter().enumerate().rev().find_map(|(position, pick)| {
+            let picked = self.text[pick.index..].chars().next()?;
+            let next = next_char(&pick.ranges, picked)?;
+            Some(Candidate::new(format!("{}{}", &self.text[..pick.index], next), self.picks[..=position].to_vec()))
+        })
+    }
+}
+
+/// Returns the smallest char out of the given ranges that comes after the given one.
+fn next_char(ranges: &[(char, char)], current: char) -> Option<char> {
+    ranges.iter()
+        .filter_map(|(start, end)| ((current as u32 + 1).max(*start as u32)..=*end as u32).find_map(char::from_u32))
+        .min()
+}
+
+impl<'a> Regex<'a> {
+    /// Returns what can be appended to the given partial input to continue a match, as found
+    /// in the literals and classes of this Regex where the input ends. The input itself might
+    /// match already. Custom matchers and Not cannot tell what they expect, so they add nothing.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::completion::Completion;
+    ///
+    /// let regex = Regex::parse(r"color: (red|green|\d+)").unwrap();
+    /// let completions = regex.completions("color: ").unwrap();
+    ///
+    /// assert_eq!(vec![
+    ///     Completion::Literal(String::from("red")),
+    ///     Completion::Literal(String::from("green")),
+    ///     Completion::Chars(vec![('0', '9')]),
+    /// ], completions);
+    /// assert_eq!(vec![Completion::Literal(String::from("een"))], regex.completions("color: gr").unwrap());
+    /// ```
+    pub fn completions(&self, input: &str) -> Result<Vec<Completion>, MatchError> {
+        let (_, completions) = self.complete(input)?;
+        Ok(completions)
+    }
+
+    /// Returns the shortest string that makes the given partial input match when appended,
+    /// which is empty if it matches already. Returns None if there is none, or if none was
+    /// found within the first DEFAULT_COMPLETION_LIMIT candidates.
+    ///
+    /// Of the chars a class allows, the first printable one is tried. Others are only tried
+    /// when nothing can follow a candidate anymore, so the result is a heuristic: a longer
+    /// completion might be found, or None, when another char would lead further.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::parse(r"(https?|ftp)://[a-z]+\.com").unwrap();
+    ///
+    /// assert_eq!(Some(String::from("p://a.com")), regex.shortest_completion("ft").unwrap());
+    /// assert_eq!(None, regex.shortest_completion("gopher").unwrap());
+    /// ```
+    pub fn shortest_completion(&self, input: &str) -> Result<Option<String>, MatchError> {
+        self.shortest_completion_within(input, DEFAULT_COMPLETION_LIMIT)
+    }
+
+    /// Like shortest_completion, but tries at most the given number of candidates.
+    pub fn shortest_completion_within(&self, input: &str, limit: usize) -> Result<Option<String>, MatchError> {
+        // Candidates are tried shortest first, so the first one that matches is the shortest one tried
+        let mut candidates = BinaryHeap::new();
+        let mut seen = HashSet::new();
+        candidates.push(Reverse(Candidate::default()));
+
+        for _ in 0..limit {
+            let Reverse(candidate) = match candidates.pop() {
+                Some(candidate) => candidate,
+                None => return Ok(None)
+            };
+
+            let (complete, completions) = self.complete(&format!("{}{}", input, candidate.text))?;
+            if complete {
+                return Ok(Some(candidate.text));
+            }
+
+            // Nothing can follow, but another char picked from a class might lead further
+            let others = match completions.is_empty() {
+                true => candidate.other_pick(),
+                false => None
+            };
+            for extended in completions.iter().map(|completion| candidate.extended(completion)).chain(others) {
+                if seen.insert((extended.text.clone(), extended.picks.clone())) {
+                    candidates.push(Reverse(extended));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Matches the given input along every possible way and returns if any of them matches
+    /// it completely, together with everything that could follow it.
+    fn complete(&self, input: &str) -> Result<(bool, Vec<Completion>), MatchError> {
+        let mut state = MatchState::new(input, self.recursion_limit).with_groups_of(self).collecting_completions();
+        let mut complete = false;
+
+        self.matches_string(&mut state, &mut |state| {
+            complete |= state.cursor.at_the_end();
+            Ok(false)
+        })?;
+        Ok((complete, state.completions().to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::class::Class;
+    use crate::regex::completion::Completion;
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_completions() {
+        let regex = Regex::from("foo") + (Regex::from("bar") | "baz");
+
+        assert_eq!(vec![Completion::Literal(String::from("oo"))], regex.completions("f").unwrap());
+        assert_eq!(vec![Completion::Literal(String::from("ar")), Completion::Literal(String::from("az"))], regex.completions("foob").unwrap());
+        assert_eq!(Vec::<Completion>::new(), regex.completions("fooq").unwrap())
+    }
+
+    #[test]
+    fn success_completions_after_match() {
+        let regex = Regex::matcher(Class::digit()).that_repeats(ZeroToInfinity);
+
+        assert_eq!(vec![Completion::Chars(vec![('0', '9')])], regex.completions("12").unwrap())
+    }
+
+    #[test]
+    fn success_completions_of_backreference() {
+        let regex = Regex::group(Regex::from("ab") | "cd") + "=" + Regex::backreference(1);
+
+        assert_eq!(vec![Completion::Literal(String::from("d"))], regex.completions("cd=c").unwrap())
+    }
+
+    #[test]
+    fn success_shortest_completion() {
+        let regex = Regex::from("a") + Regex::from("bc").that_repeats(ZeroToInfinity) + "d";
+
+        assert_eq!(Some(String::from("d")), regex.shortest_completion("abc").unwrap());
+        assert_eq!(Some(String::from("cd")), regex.shortest_completion("ab").unwrap());
+        assert_eq!(Some(String::new()), regex.shortest_completion("ad").unwrap());
+        assert_eq!(None, regex.shortest_completion("ax").unwrap())
+    }
+
+    #[test]
+    fn success_shortest_completion_tries_other_chars() {
+        let regex = Regex::parse("(?!a)[a-z]").unwrap();
+        let both = Regex::and(Regex::parse("[a-z]x").unwrap(), Regex::parse("b.").unwrap());
+
+        assert_eq!(Some(String::from("b")), regex.shortest_completion("").unwrap());
+        assert_eq!(Some(String::from("bx")), both.shortest_completion("").unwrap())
+    }
+
+    #[test]
+    fn failure_shortest_completion_limit() {
+        let regex = Regex::matcher(Class::of("ab")).that_repeats(Times::new(20));
+
+        assert_eq!(None, regex.shortest_completion_within("", 10).unwrap());
+        assert_eq!(Some("a".repeat(20)), regex.shortest_completion("").unwrap())
+    }
+}
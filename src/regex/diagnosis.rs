@@ -0,0 +1,63 @@
+use std::fmt::{Display, Formatter};
+
+/// Explains why a string did not match a Regex: the furthest position
+/// matching reached, what was expected there and what was found instead.
+#[derive(Debug, PartialEq)]
+pub struct Diagnosis {
+    /// The index of the char where matching got stuck, starting at 0.
+    pub position: usize,
+    /// Descriptions of everything that would have allowed matching to go on.
+    pub expected: Vec<String>,
+    /// The char at the position, or None if the end of the string was reached.
+    pub found: Option<char>,
+}
+
+impl Diagnosis {
+    /// The column of the position, starting at 1.
+    pub fn column(&self) -> usize {
+        self.position + 1
+    }
+}
+
+impl Display for Diagnosis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let expected = match self.expected.split_last() {
+            None => String::from("nothing"),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last)
+        };
+        let found = match self.found {
+            Some(c) => format!("`{}`", c),
+            None => String::from("the end of the string")
+        };
+
+        write!(f, "at column {}: expected {}, found {}", self.column(), expected, found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::diagnosis::Diagnosis;
+
+    #[test]
+    fn success_display() {
+        let diagnosis = Diagnosis {
+            position: 6,
+            expected: vec![String::from("`bar`"), String::from("`baz`"), String::from("a digit")],
+            found: Some('x'),
+        };
+
+        assert_eq!("at column 7: expected `bar`, `baz` or a digit, found `x`", diagnosis.to_string())
+    }
+
+    #[test]
+    fn success_display_at_the_end() {
+        let diagnosis = Diagnosis {
+            position: 3,
+            expected: vec![String::from("`bar`")],
+            found: None,
+        };
+
+        assert_eq!("at column 4: expected `bar`, found the end of the string", diagnosis.to_string())
+    }
+}
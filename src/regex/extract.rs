@@ -0,0 +1,292 @@
+use std::cell::OnceCell;
+use std::rc::Rc;
+
+use crate::regex::Regex;
+use crate::regex::match_state::{GroupNumbering, MatchState};
+use crate::regex::outcome::MatchError;
+use crate::repeat::Repeat;
+
+/// The rest of an extraction. It gets called with every end position and value
+/// an Extractor could produce and returns if the remaining extraction succeeds from there.
+type ValueContinuation<'c, V> = dyn FnMut(&mut MatchState, usize, V) -> Result<bool, MatchError> + 'c;
+
+/// Produces the value of one part, starting at the given position.
+type ExtractFn<'a, V> = dyn Fn(&mut MatchState, usize, &mut ValueContinuation<V>) -> Result<bool, MatchError> + 'a;
+
+/// Turns the text a Regex matches into a value of type V, in the style of parser combinators.
+/// Extractors are created with Regex::map or Regex::try_map and combined into tuples
+/// with then, into alternatives with or, and into lists with repeated.
+///
+/// Every part is matched like the regex it was created from. Of all ways the parts can
+/// split a string, the one a single regex made of them would find first is taken.
+/// A part whose mapping returns None does not match, so other ways are tried.
+/// Like for a Regex, only the recursion limit of the part matching starts with counts.
+///
+/// Examples:
+/// ```
+/// use crate::readex::regex::Regex;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Setting { Number(u32), Flag(bool) }
+///
+/// let name = Regex::parse("[a-z]+").unwrap().map(str::to_string);
+/// let number = Regex::parse(r"\d+").unwrap().try_map(|text| text.parse().ok()).map(Setting::Number);
+/// let flag = Regex::parse("on|off").unwrap().map(|text| text == "on").map(Setting::Flag);
+/// let setting = name.followed_by(Regex::from("=")).then(number.or(flag));
+///
+/// assert_eq!(Some((String::from("retries"), Setting::Number(3))), setting.extract("retries=3").unwrap());
+/// assert_eq!(Some((String::from("verbose"), Setting::Flag(false))), setting.extract("verbose=off").unwrap());
+/// assert_eq!(None, setting.extract("verbose=maybe").unwrap());
+/// ```
+pub struct Extractor<'a, V> {
+    extract: Rc<ExtractFn<'a, V>>,
+    recursion_limit: usize,
+}
+
+impl<'a, V: 'a> Extractor<'a, V> {
+    fn new(recursion_limit: usize, extract: impl Fn(&mut MatchState, usize, &mut ValueContinuation<V>) -> Result<bool, MatchError> + 'a) -> Self {
+        Extractor {
+            extract: Rc::new(extract),
+            recursion_limit,
+        }
+    }
+
+    /// Returns the value extracted from the whole given string, or None if it does not match.
+    pub fn extract(&self, string: &str) -> Result<Option<V>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit);
+        let length = string.chars().count();
+        let mut extracted = None;
+
+        (self.extract)(&mut state, 0, &mut |_, end, value| {
+            if end != length {
+                return Ok(false);
+            }
+            extracted = Some(value);
+            Ok(true)
+        })?;
+        Ok(extracted)
+    }
+
+    /// Searches the given string for the first part this Extractor matches and returns
+    /// its start and end char index together with the extracted value.
+    pub fn find(&self, string: &str) -> Result<Option<(usize, usize, V)>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit);
+        let mut found = None;
+
+        for start in 0..=string.chars().count() {
+            (self.extract)(&mut state, start, &mut |_, end, value| {
+                found = Some((start, end, value));
+                Ok(true)
+            })?;
+            if found.is_some() {
+                break;
+            }
+        }
+        Ok(found)
+    }
+
+    /// Turns the extracted values into other ones with the given function.
+    pub fn map<W: 'a>(self, function: impl Fn(V) -> W + 'a) -> Extractor<'a, W> {
+        Extractor::new(self.recursion_limit, move |state, start, next| {
+            (self.extract)(state, start, &mut |state, end, value| next(state, end, function(value)))
+        })
+    }
+
+    /// Combines this Extractor with one that has to match right after it. The values of both form a tuple.
+    pub fn then<W: 'a>(self, other: Extractor<'a, W>) -> Extractor<'a, (V, W)> where V: Clone {
+        Extractor::new(self.recursion_limit, move |state, start, next| {
+            (self.extract)(state, start, &mut |state, middle, left| {
+                (other.extract)(state, middle, &mut |state, end, right| next(state, end, (left.clone(), right)))
+            })
+        })
+    }
+
+    /// Combines this Extractor with another one, so that either of them has to match.
+    /// This one is tried first.
+    pub fn or(self, other: Extractor<'a, V>) -> Self {
+        Extractor::new(self.recursion_limit, move |state, start, next| {
+            Ok((self.extract)(state, start, next)? || (other.extract)(state, start, next)?)
+        })
+    }
+
+    /// Lets the given regex match after this Extractor, without changing the value.
+    pub fn followed_by(self, regex: Regex<'a>) -> Self where V: Clone {
+        self.then(regex.map(|_| ())).map(|(value, _)| value)
+    }
+
+    /// Lets the given regex match before this Extractor, without changing the value.
+    pub fn preceded_by(self, regex: Regex<'a>) -> Self {
+        regex.map(|_| ()).then(self).map(|(_, value)| value)
+    }
+
+    /// Lets this Extractor repeat as the given Repeat says and collects the values of all repeats.
+    /// Like a repeated Regex, it repeats as often as possible.
+    pub fn repeated(self, repeat: impl Repeat) -> Extractor<'a, Vec<V>> where V: Clone {
+        let minimum = repeat.get_minimum().unwrap_or(0);
+        let maximum = repeat.get_maximum();
+        Extractor::new(self.recursion_limit, move |state, start, next| self.repeat_from(state, start, vec![], minimum, maximum, next))
+    }
+
+    /// Extracts further repeats beginning at the given position, after the given values.
+    fn repeat_from(&self, state: &mut MatchState, start: usize, values: Vec<V>, minimum: usize, maximum: Option<usize>, next: &mut ValueContinuation<Vec<V>>) -> Result<bool, MatchError> where V: Clone {
+        let count = values.len();
+        if maximum.is_none_or(|maximum| count < maximum) {
+            let repeat_matches = (self.extract)(state, start, &mut |state, end, value| {
+                // A repeat that consumed nothing would loop forever
+                if count >= minimum && end == start {
+                    return Ok(false);
+                }
+                let mut values = values.clone();
+                values.push(value);
+
+                state.enter()?;
+                let result = self.repeat_from(state, end, values, minimum, maximum, next);
+                state.exit();
+                result
+            })?;
+
+            if repeat_matches {
+                return Ok(true);
+            }
+        }
+
+        match count >= minimum {
+            true => next(state, start, values),
+            false => Ok(false)
+        }
+    }
+}
+
+impl<'a, V> Clone for Extractor<'a, V> {
+    fn clone(&self) -> Self {
+        Extractor {
+            extract: Rc::clone(&self.extract),
+            recursion_limit: self.recursion_limit,
+        }
+    }
+}
+
+impl<'a> Regex<'a> {
+    /// Creates an Extractor that turns the text this Regex matches into a value.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let digits = Regex::parse(r"\d+").unwrap().map(|text| text.len());
+    ///
+    /// assert_eq!(Some(3), digits.extract("123").unwrap());
+    /// ```
+    pub fn map<V: 'a>(self, function: impl Fn(&str) -> V + 'a) -> Extractor<'a, V> {
+        self.try_map(move |text| Some(function(text)))
+    }
+
+    /// Creates an Extractor like map, but a text the given function returns None for
+    /// does not match. Other ways to match are tried then.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let byte = Regex::parse(r"\d+").unwrap().try_map(|text| text.parse::<u8>().ok());
+    ///
+    /// assert_eq!(Some(255), byte.extract("255").unwrap());
+    /// assert_eq!(None, byte.extract("256").unwrap());
+    /// ```
+    pub fn try_map<V: 'a>(self, function: impl Fn(&str) -> Option<V> + 'a) -> Extractor<'a, V> {
+        // The parts share one MatchState, so every part sets its groups again
+        let groups = OnceCell::new();
+        Extractor::new(self.recursion_limit, move |state, start, next| {
+            let groups = groups.get_or_init(|| Rc::new(GroupNumbering::of(self.groups())));
+            state.set_group_numbering(Rc::clone(groups));
+            for end in self.ends_from(state, start)? {
+                let text = state.cursor.text(start, end).unwrap_or_default();
+                if let Some(value) = function(&text) {
+                    state.cursor.set_index(end);
+                    if next(state, end, value)? {
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        })
+    }
+
+    /// Returns every position a match of this Regex beginning at the given one can end at,
+    /// in the order matching tries them.
+    fn ends_from(&self, state: &mut MatchState, start: usize) -> Result<Vec<usize>, MatchError> {
+        state.cursor.set_index(start);
+
+        let mut ends = vec![];
+        self.matches_string(state, &mut |state| {
+            let end = state.cursor.index();
+            if !ends.contains(&end) {
+                ends.push(end);
+            }
+            Ok(false)
+        })?;
+        Ok(ends)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::extract::Extractor;
+    use crate::regex::outcome::MatchError;
+    use crate::regex::Regex;
+    use crate::repeat::between::Between;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    fn number<'a>() -> Extractor<'a, u32> {
+        Regex::parse(r"\d+").unwrap().try_map(|text| text.parse().ok())
+    }
+
+    #[test]
+    fn success_then() {
+        let pair = number().followed_by(Regex::from(",")).then(number());
+
+        assert_eq!(Some((12, 345)), pair.extract("12,345").unwrap());
+        assert_eq!(None, pair.extract("12,").unwrap())
+    }
+
+    #[test]
+    fn success_then_splits_like_the_regex() {
+        let letters = Regex::parse("a*").unwrap().map(str::to_string);
+        let pair = letters.clone().then(letters);
+
+        assert_eq!(Some((String::from("aaa"), String::new())), pair.extract("aaa").unwrap())
+    }
+
+    #[test]
+    fn success_repeated() {
+        let list = number().followed_by(Regex::from(";")).repeated(ZeroToInfinity);
+
+        assert_eq!(Some(vec![1, 22, 333]), list.extract("1;22;333;").unwrap());
+        assert_eq!(Some(vec![]), list.extract("").unwrap());
+        assert_eq!(None, number().repeated(Between::new(2, Some(3))).extract("1").unwrap())
+    }
+
+    #[test]
+    fn success_try_map_backtracks() {
+        let small = Regex::parse(r"\d+").unwrap().try_map(|text| text.parse::<u8>().ok());
+        let parts = small.clone().then(small);
+
+        assert_eq!(Some((100, 200)), parts.extract("100200").unwrap())
+    }
+
+    #[test]
+    fn success_find() {
+        let found = number().preceded_by(Regex::from("#")).find("see #42 and #7").unwrap();
+
+        assert_eq!(Some((4, 7, 42)), found)
+    }
+
+    #[test]
+    fn failure_own_recursion_limit_exceeded() {
+        let letters = Regex::parse("(a|b)*").unwrap().with_recursion_limit(10).map(str::len);
+        let input = "ab".repeat(20);
+
+        assert_eq!(Err(MatchError::RecursionLimitExceeded(10)), letters.extract(&input));
+        assert_eq!(Ok(Some(40)), Regex::parse("(a|b)*").unwrap().map(str::len).extract(&input))
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::fmt::Formatter;
+
+use crate::matcher::class::range_size;
+use crate::regex::backreference::GroupReference;
+use crate::regex::group::Group;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+
+/// How many chars a generated string may have by default.
+pub const DEFAULT_SIZE_LIMIT: usize = 1_000;
+
+/// How often generating a string is tried by default, before giving up.
+pub const DEFAULT_ATTEMPTS: usize = 100;
+
+/// The chars inserted into strings to turn them into near misses.
+const NEAR_MISS_CHARS: (char, char) = (' ', '~');
+
+/// Generates random strings that match a Regex, or that barely do not match it.
+/// Generating is deterministic for a given seed, so failing test data can be reproduced.
+///
+/// Repeats without a maximum, like ZeroToInfinity, repeat a random number of times
+/// within the repeat bounds. And, Not and backreferences are generated by trying until
+/// a string fits, lookarounds, rule references and custom matchers are not supported.
+///
+/// Examples:
+/// ```
+/// use crate::readex::regex::Regex;
+/// use crate::readex::regex::generator::Generator;
+/// use crate::readex::matcher::class::Class;
+/// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+///
+/// let regex = Regex::from("id-") + Regex::matcher(Class::digit()).that_repeats(ZeroToInfinity);
+/// let mut generator = Generator::new(42).with_repeat_bounds(1, 4);
+///
+/// let string = generator.generate(&regex).unwrap();
+/// assert!(regex.matches(&string));
+///
+/// let near_miss = generator.generate_near_miss(&regex).unwrap();
+/// assert!(!regex.matches(&near_miss));
+/// ```
+pub struct Generator {
+    random: SplitMix64,
+    repeat_bounds: (usize, usize),
+    size_limit: usize,
+    attempts: usize,
+    group_keys: Vec<(*const (), Option<String>)>,
+    captures: HashMap<*const (), String>,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            random: SplitMix64 { state: seed },
+            repeat_bounds: (0, 8),
+            size_limit: DEFAULT_SIZE_LIMIT,
+            attempts: DEFAULT_ATTEMPTS,
+            group_keys: vec![],
+            captures: HashMap::new(),
+        }
+    }
+
+    /// Sets how often repeats without a maximum repeat at least and at most.
+    /// The minimum of the repeat itself is still respected.
+    pub fn with_repeat_bounds(mut self, minimum: usize, maximum: usize) -> Self {
+        self.repeat_bounds = (minimum, maximum.max(minimum));
+        self
+    }
+
+    /// Sets how many chars a generated string may have.
+    pub fn with_size_limit(mut self, size_limit: usize) -> Self {
+        self.size_limit = size_limit;
+        self
+    }
+
+    /// Sets how often generating a string is tried, before giving up.
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Generates a random string the given Regex matches.
+    pub fn generate(&mut self, regex: &Regex) -> Result<String, GenerateError> {
+        self.number_groups_of(regex);
+        self.attempt(|generator| {
+            generator.captures.clear();
+            generator.generate_matching_once(regex, regex)
+        })
+    }
+
+    /// Generates a random string the given Regex does not match, but which
+    /// differs from a matching string by one or two inserted, removed or replaced chars.
+    pub fn generate_near_miss(&mut self, regex: &Regex) -> Result<String, GenerateError> {
+        self.number_groups_of(regex);
+        self.attempt(|generator| {
+            generator.captures.clear();
+            generator.generate_near_miss_once(regex)
+        })
+    }
+
+    /// Generates a string for the first Regex that the second one matches as well.
+    pub(crate) fn generate_matching<T>(&mut self, regex: &Regex<T>, check: &Regex<T>) -> Result<String, GenerateError> {
+        self.attempt(|generator| generator.generate_matching_once(regex, check))
+    }
+
+    /// Generates a string that the given Regex, which is part of the generated one, does not match.
+    pub(crate) fn generate_near_miss_of<T>(&mut self, regex: &Regex<T>) -> Result<String, GenerateError> {
+        self.attempt(|generator| generator.generate_near_miss_once(regex))
+    }
+
+    fn generate_matching_once<T>(&mut self, regex: &Regex<T>, check: &Regex<T>) -> Result<String, GenerateError> {
+        let string = self.generate_once(regex)?;
+        match check.try_matches_text(&string)? {
+            true => Ok(string),
+            false => Err(GenerateError::NoStringFound(1))
+        }
+    }
+
+    fn generate_near_miss_once<T>(&mut self, regex: &Regex<T>) -> Result<String, GenerateError> {
+        let string = self.generate_once(regex)?;
+        let edits = 1 + self.random_below(2);
+        let near_miss = (0..edits).fold(string, |string, _| self.edit(string));
+        match regex.try_matches_text(&near_miss)? {
+            true => Err(GenerateError::NoStringFound(1)),
+            false => Ok(near_miss)
+        }
+    }
+
+    fn number_groups_of<T>(&mut self, regex: &Regex<T>) {
+        self.group_keys = regex.groups().into_iter()
+            .map(|group| (group_key(group), group.name().map(String::from)))
+            .collect();
+    }
+
+    /// Generates one string for the given Regex, which it does not necessarily match yet.
+    pub(crate) fn generate_once<T>(&mut self, regex: &Regex<T>) -> Result<String, GenerateError> {
+        let mut output = String::new();
+        regex.generate_into(self, &mut output)?;
+        Ok(output)
+    }
+
+    /// Calls the given function until it succeeds, fails with an unsupported regex or an error
+    /// while matching, or was called as often as the attempts allow.
+    fn attempt<R>(&mut self, mut function: impl FnMut(&mut Self) -> Result<R, GenerateError>) -> Result<R, GenerateError> {
+        let mut last_error = GenerateError::NoStringFound(self.attempts);
+        for _ in 0..self.attempts {
+            match function(self) {
+                Ok(result) => return Ok(result),
+                Err(GenerateError::Unsupported(node)) => return Err(GenerateError::Unsupported(node)),
+                Err(GenerateError::Match(error)) => return Err(GenerateError::Match(error)),
+                Err(GenerateError::SizeLimitExceeded(limit)) => last_error = GenerateError::SizeLimitExceeded(limit),
+                Err(GenerateError::NoStringFound(_)) => {}
+            }
+        }
+
+        Err(match last_error {
+            GenerateError::NoStringFound(_) => GenerateError::NoStringFound(self.attempts),
+            error => error
+        })
+    }
+
+    /// Inserts, removes or replaces one random char of the given string.
+    fn edit(&mut self, string: String) -> String {
+        let mut chars: Vec<char> = string.chars().collect();
+        let position = self.random_below(chars.len() + 1);
+        let c = self.random_char(&[NEAR_MISS_CHARS]);
+
+        match (self.random_below(3), position < chars.len()) {
+            (1, true) => { chars.remove(position); }
+            (2, true) => chars[position] = c,
+            _ => chars.insert(position, c)
+        }
+        chars.into_iter().collect()
+    }
+
+    /// Returns how often a regex with the given repeat bounds should repeat.
+    pub(crate) fn repeat_count(&mut self, minimum: usize, maximum: Option<usize>) -> usize {
+        let maximum = maximum.unwrap_or_else(|| minimum.max(self.repeat_bounds.1));
+        let minimum = match maximum == minimum {
+            true => minimum,
+            false => minimum.max(self.repeat_bounds.0).min(maximum)
+        };
+        minimum + self.random_below(maximum - minimum + 1)
+    }
+
+    /// Fails if the given output got longer than the size limit.
+    pub(crate) fn check_size(&self, output: &str) -> Result<(), GenerateError> {
+        match output.chars().count() > self.size_limit {
+            true => Err(GenerateError::SizeLimitExceeded(self.size_limit)),
+            false => Ok(())
+        }
+    }
+
+    /// Returns a random number below the given bound, which must not be 0.
+    pub(crate) fn random_below(&mut self, bound: usize) -> usize {
+        (self.random.next() % bound as u64) as usize
+    }
+
+    /// Returns a random char out of the given ranges, each char being equally likely.
+    pub(crate) fn random_char(&mut self, ranges: &[(char, char)]) -> char {
+        let total: u64 = ranges.iter().map(|(start, end)| range_size(*start, *end) as u64).sum();
+        let mut index = self.random.next() % total.max(1);
+        for (start, end) in ranges {
+            let size = range_size(*start, *end) as u64;
+            if index < size {
+                let mut code = *start as u32 + index as u32;
+                if *start <= '\u{D7FF}' && code > 0xD7FF {
+                    code += 0x800;
+                }
+                return char::from_u32(code).unwrap_or(*start);
+            }
+            index -= size;
+        }
+        NEAR_MISS_CHARS.0
+    }
+
+    /// Remembers the text the given group generated, for backreferences.
+    pub(crate) fn set_capture<T>(&mut self, group: &Group<T>, text: String) {
+        self.captures.insert(group_key(group), text);
+    }
+
+    /// Returns the text the referenced group generated, or None if it did not generate anything yet.
+    pub(crate) fn captured_text(&self, reference: &GroupReference) -> Option<String> {
+        let key = match reference {
+            GroupReference::Number(number) => self.group_keys.get(number.checked_sub(1)?)?.0,
+            GroupReference::Name(name) => self.group_keys.iter().find(|(_, group_name)| group_name.as_deref() == Some(name))?.0
+        };
+        self.captures.get(&key).cloned()
+    }
+}
+
+/// Everything that can keep a Generator from generating a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// The regex contains the given node, which cannot be generated.
+    Unsupported(String),
+    /// Every generated string was longer than the given size limit.
+    SizeLimitExceeded(usize),
+    /// No fitting string was found in the given number of attempts.
+    NoStringFound(usize),
+    /// Checking whether a generated string matches failed with the given error.
+    Match(MatchError),
+}
+
+impl From<MatchError> for GenerateError {
+    fn from(error: MatchError) -> Self {
+        GenerateError::Match(error)
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl std::fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported(node) => write!(f, "{} cannot be generated!", node),
+            Self::SizeLimitExceeded(limit) => write!(f, "Every generated string exceeded the size limit of {}!", limit),
+            Self::NoStringFound(attempts) => write!(f, "No fitting string was found in {} attempts!", attempts),
+            Self::Match(error) => write!(f, "Checking a generated string failed: {}", error)
+        }
+    }
+}
+
+/// A small, fast pseudo random number generator, good enough for test data.
+struct SplitMix64 {
+    state: u64
+}
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+fn group_key<T>(group: &Group<T>) -> *const () {
+    group as *const Group<T> as *const ()
+}
+
+impl<'a, T> Regex<'a, T> {
+    /// Appends a random string for this Regex and its following regexes to the given output.
+    pub(crate) fn generate_into(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        let count = match &self.repeat {
+            Some(repeat) => generator.repeat_count(repeat.get_minimum().unwrap_or(0), repeat.get_maximum()),
+            None => 1
+        };
+        for _ in 0..count {
+            self.regex_type.generate(generator, output)?;
+            generator.check_size(output)?;
+        }
+
+        match &self.next {
+            Some(next_regex) => next_regex.generate_into(generator, output),
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::matcher::class::Class;
+    use crate::regex::generator::{GenerateError, Generator};
+    use crate::regex::outcome::MatchError;
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_generate() {
+        let word = Regex::matcher(Class::new(&[('a', 'z')])).that_repeats(ZeroToInfinity);
+        let regex = Regex::group(word) + "=" + (Regex::from("on") | "off") + ";" + Regex::backreference(1);
+        let mut generator = Generator::new(7);
+
+        for _ in 0..50 {
+            let string = generator.generate(&regex).unwrap();
+            assert!(regex.matches(&string), "{}", string)
+        }
+    }
+
+    #[test]
+    fn success_generate_is_deterministic() {
+        let regex = Regex::matcher(Any).that_repeats(Times::new(5)) | Regex::from("x");
+
+        assert_eq!(Generator::new(3).generate(&regex), Generator::new(3).generate(&regex))
+    }
+
+    #[test]
+    fn success_generate_respects_repeat_bounds() {
+        let regex = Regex::from("a").that_repeats(ZeroToInfinity);
+        let mut generator = Generator::new(1).with_repeat_bounds(2, 3);
+
+        for _ in 0..20 {
+            let length = generator.generate(&regex).unwrap().len();
+            assert!((2..=3).contains(&length))
+        }
+    }
+
+    #[test]
+    fn success_generate_with_and_and_not() {
+        let regex = Regex::and(Regex::matcher(Class::of("ab")).that_repeats(Times::new(2)), Regex::not(Regex::from("aa")));
+        let mut generator = Generator::new(11);
+
+        for _ in 0..20 {
+            let string = generator.generate(&regex).unwrap();
+            assert!(regex.matches(&string), "{}", string)
+        }
+    }
+
+    #[test]
+    fn success_generate_near_miss() {
+        let regex = Regex::from("key") + Regex::matcher(Class::digit()).that_repeats(Times::new(2));
+        let mut generator = Generator::new(5);
+
+        for _ in 0..20 {
+            let string = generator.generate_near_miss(&regex).unwrap();
+            assert!(!regex.matches(&string), "{}", string)
+        }
+    }
+
+    #[test]
+    fn failure_generate_size_limit_exceeded() {
+        let regex = Regex::from("abc").that_repeats(Times::new(10));
+        let mut generator = Generator::new(0).with_size_limit(20).with_attempts(3);
+
+        assert_eq!(Err(GenerateError::SizeLimitExceeded(20)), generator.generate(&regex))
+    }
+
+    #[test]
+    fn failure_generate_unsupported() {
+        let regex = Regex::lookahead(Regex::from("a")) + "a";
+
+        assert!(matches!(Generator::new(0).generate(&regex), Err(GenerateError::Unsupported(_))))
+    }
+
+    #[test]
+    fn failure_generate_match_error() {
+        let regex = (Regex::from("a") | "b").that_repeats(Times::new(50)).with_recursion_limit(10);
+        let error = Err(GenerateError::Match(MatchError::RecursionLimitExceeded(10)));
+
+        assert_eq!(error, Generator::new(0).generate(&regex));
+        assert_eq!(error, Generator::new(0).generate_near_miss(&regex))
+    }
+
+    #[test]
+    fn failure_generate_near_miss_of_anything() {
+        let regex = Regex::matcher(Any).that_repeats(ZeroToInfinity);
+
+        assert_eq!(Err(GenerateError::NoStringFound(5)), Generator::new(0).with_attempts(5).generate_near_miss(&regex))
+    }
+}
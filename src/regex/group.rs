@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
+
+/// Captures the part of the string its inner regex matches. Groups are numbered
+/// from left to right, starting with 1. A group can also have a name.
+pub struct Group<'a, T = char> {
+    name: Option<String>,
+    inner: Regex<'a, T>,
+}
+
+impl<'a, T> RegexType<'a, T> for Group<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let index = state.group_index(self)?;
+        let start = state.cursor.index();
+
+        self.inner.matches_string(state, &mut |state| {
+            let end = state.cursor.index();
+            let former_capture = state.set_capture(index, Some((start, end)));
+            let next_matches = next(state)?;
+
+            if !next_matches {
+                state.set_capture(index, former_capture);
+            }
+            Ok(next_matches)
+        })
+    }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![&self.inner]
+    }
+
+    fn group(&self) -> Option<&Group<'a, T>> {
+        Some(self)
+    }
+
+    fn single_end(&self) -> bool {
+        self.inner.has_single_end()
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        self.inner.nullable(nullable_rules)
+    }
+
+    fn literals(&self) -> Literals {
+        self.inner.literals()
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        self.inner.to_nfa(nfa, alphabet)
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        let start = output.len();
+        self.inner.generate_into(generator, output)?;
+        generator.set_capture(self, String::from(&output[start..]));
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        let group = *self;
+        Regex::new_regex(Group::new(group.name.as_deref(), group.inner.simplified(stats)))
+    }
+}
+
+impl<'a, T> Group<'a, T> {
+    pub fn new(name: Option<&str>, inner: Regex<'a, T>) -> Self {
+        Group {
+            name: name.map(String::from),
+            inner,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl<'a, T> Display for Group<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "Group <{}>", name),
+            None => write!(f, "Group")
+        }
+    }
+}
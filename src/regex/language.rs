@@ -0,0 +1,251 @@
+use crate::automaton::{AutomatonError, DEFAULT_STATE_LIMIT, MatchCount, Verdict};
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::dfa::Dfa;
+use crate::automaton::enumeration::Enumeration;
+use crate::automaton::nfa::{Fragment, Nfa};
+use crate::matcher::class::range_size;
+use crate::regex::Regex;
+
+/// Char sets with more chars than this, like Any, only add their printable
+/// ASCII chars to the default alphabet of an Enumeration.
+const LARGE_CHAR_SET: u32 = 128;
+
+impl<'a> Regex<'a> {
+    /// Returns if this Regex matches exactly the same strings as the other one.
+    /// If not, the shortest string only one of them matches is returned as counterexample.
+    ///
+    /// Backreferences, lookarounds, rule references and custom matchers cannot be
+    /// turned into an automaton, so comparing regexes containing them fails.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+    /// use crate::readex::automaton::Verdict;
+    ///
+    /// let twice = (Regex::from("a") + "a").that_repeats(ZeroToInfinity);
+    /// let any = Regex::from("a").that_repeats(ZeroToInfinity);
+    ///
+    /// assert!(twice.is_equivalent(&Regex::from("aa").that_repeats(ZeroToInfinity)).unwrap().holds());
+    /// assert_eq!(Verdict::Counterexample(String::from("a")), twice.is_equivalent(&any).unwrap());
+    /// ```
+    pub fn is_equivalent(&self, other: &Regex) -> Result<Verdict, AutomatonError> {
+        self.compare(other, |own, other| own != other)
+    }
+
+    /// Returns if every string this Regex matches is matched by the other one as well.
+    /// If not, the shortest string only this Regex matches is returned as counterexample.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::class::Class;
+    ///
+    /// let digit = Regex::matcher(Class::digit());
+    ///
+    /// assert!(Regex::from("7").is_subset_of(&digit).unwrap().holds());
+    /// assert_eq!(Some("0"), digit.is_subset_of(&Regex::from("7")).unwrap().counterexample());
+    /// ```
+    pub fn is_subset_of(&self, other: &Regex) -> Result<Verdict, AutomatonError> {
+        self.compare(other, |own, other| own && !other)
+    }
+
+    /// Builds a Dfa that accepts exactly the strings this Regex matches.
+    pub fn to_dfa(&self) -> Result<Dfa, AutomatonError> {
+        self.to_dfa_over(&Alphabet::new(&self.char_sets()))
+    }
+
+    /// Counts the strings of the given length this Regex matches, using its automaton
+    /// instead of trying them. Also tells if the Regex matches only finitely many strings.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::class::Class;
+    /// use crate::readex::repeat::times::Times;
+    ///
+    /// let pin = Regex::matcher(Class::digit()).that_repeats(Times::new(4));
+    /// let count = pin.count_matches_of_length(4).unwrap();
+    ///
+    /// assert_eq!(Some(10_000), count.count);
+    /// assert!(count.finite);
+    /// ```
+    pub fn count_matches_of_length(&self, length: usize) -> Result<MatchCount, AutomatonError> {
+        let dfa = self.to_dfa()?;
+        Ok(MatchCount {
+            count: dfa.count_of_length(length),
+            finite: dfa.is_finite(),
+        })
+    }
+
+    /// Returns an iterator over every string this Regex matches, up to the given length,
+    /// shorter strings first and strings of the same length in the order of their chars.
+    ///
+    /// By default, all chars the matchers of this Regex check are used, but from large
+    /// char sets like Any only the printable ASCII chars. Use Enumeration::with_alphabet
+    /// to choose the chars yourself.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::repeat::times::Times;
+    ///
+    /// let regex = (Regex::from("a") | "b").that_repeats(Times::new(2));
+    /// let strings: Vec<String> = regex.enumerate(5).unwrap().collect();
+    ///
+    /// assert_eq!(vec!["aa", "ab", "ba", "bb"], strings);
+    /// ```
+    pub fn enumerate(&self, max_length: usize) -> Result<Enumeration, AutomatonError> {
+        let char_sets = self.char_sets();
+        let dfa = self.to_dfa_over(&Alphabet::new(&char_sets))?;
+
+        let mut alphabet: Vec<char> = vec![];
+        for (start, end) in char_sets.iter().flatten() {
+            let (start, end) = match range_size(*start, *end) > LARGE_CHAR_SET {
+                true => ((*start).max(' '), (*end).min('~')),
+                false => (*start, *end)
+            };
+            alphabet.extend(start..=end);
+        }
+        Ok(Enumeration::new(dfa, &alphabet, max_length))
+    }
+
+    /// Searches the shortest string for which the given function, called with whether this
+    /// and the other Regex match it, returns true.
+    fn compare(&self, other: &Regex, counterexample: impl Fn(bool, bool) -> bool) -> Result<Verdict, AutomatonError> {
+        let mut char_sets = self.char_sets();
+        char_sets.extend(other.char_sets());
+        let alphabet = Alphabet::new(&char_sets);
+
+        let own_dfa = self.to_dfa_over(&alphabet)?;
+        let other_dfa = other.to_dfa_over(&alphabet)?;
+        Ok(match own_dfa.shortest_string_where(&other_dfa, counterexample) {
+            Some(string) => Verdict::Counterexample(string),
+            None => Verdict::Holds
+        })
+    }
+
+}
+
+impl<'a, T> Regex<'a, T> {
+    /// Builds a Dfa for this Regex over the given Alphabet, which must
+    /// contain every char set of this Regex as a union of atoms.
+    pub(crate) fn to_dfa_over(&self, alphabet: &Alphabet) -> Result<Dfa, AutomatonError> {
+        let mut nfa = Nfa::new();
+        let fragment = self.to_nfa(&mut nfa, alphabet)?;
+        Dfa::from_nfa(&nfa, fragment, alphabet.clone(), DEFAULT_STATE_LIMIT)
+    }
+
+    /// Returns the char sets the matchers of this Regex check.
+    pub(crate) fn char_sets(&self) -> Vec<Vec<(char, char)>> {
+        self.nodes().into_iter()
+            .flat_map(|node| match (node.regex_type.chars(), node.regex_type.literal()) {
+                (Some(chars), _) => vec![chars],
+                (None, Some(literal)) => literal.chars().map(|c| vec![(c, c)]).collect(),
+                (None, None) => vec![]
+            })
+            .collect()
+    }
+
+    /// Adds a fragment matching this Regex and its following regexes to the given Nfa.
+    pub(crate) fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        let mut fragment = self.own_fragment(nfa, alphabet)?;
+        if let Some(next_regex) = &self.next {
+            let next = next_regex.to_nfa(nfa, alphabet)?;
+            fragment = nfa.concat(fragment, next);
+        }
+        Ok(fragment)
+    }
+
+    /// Adds a fragment matching this Regex alone, with its repeat, to the given Nfa.
+    fn own_fragment(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        let repeat = match &self.repeat {
+            Some(repeat) => repeat,
+            None => return self.regex_type.to_nfa(nfa, alphabet)
+        };
+
+        let minimum = repeat.get_minimum().unwrap_or(0);
+        let mut fragment = nfa.empty();
+        for _ in 0..minimum {
+            let copy = self.regex_type.to_nfa(nfa, alphabet)?;
+            fragment = nfa.concat(fragment, copy);
+        }
+
+        let rest = match repeat.get_maximum() {
+            None => {
+                let copy = self.regex_type.to_nfa(nfa, alphabet)?;
+                nfa.star(copy)
+            }
+            Some(maximum) => {
+                let mut rest = nfa.empty();
+                for _ in minimum..maximum {
+                    let copy = self.regex_type.to_nfa(nfa, alphabet)?;
+                    let tail = nfa.concat(copy, rest);
+                    rest = nfa.optional(tail);
+                }
+                rest
+            }
+        };
+        Ok(nfa.concat(fragment, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automaton::{AutomatonError, MatchCount, Verdict};
+    use crate::matcher::any::Any;
+    use crate::matcher::class::Class;
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_is_equivalent() {
+        let factored = Regex::from("a") + (Regex::from("b") | "c");
+        let expanded = (Regex::from("a") + "b") | (Regex::from("a") + "c");
+
+        assert_eq!(Verdict::Holds, factored.is_equivalent(&expanded).unwrap());
+        assert_eq!(Verdict::Counterexample(String::from("ad")), factored.is_equivalent(&(expanded | "ad")).unwrap())
+    }
+
+    #[test]
+    fn success_is_equivalent_with_not_and_and() {
+        let not_a = Regex::not(Regex::from("a"));
+        let without_a = Regex::and(Regex::matcher(Any).that_repeats(ZeroToInfinity), Regex::not(Regex::from("a")));
+
+        assert!(not_a.is_equivalent(&without_a).unwrap().holds());
+        assert_eq!(Some(""), not_a.is_equivalent(&Regex::from("b")).unwrap().counterexample())
+    }
+
+    #[test]
+    fn success_is_subset_of_with_repeats() {
+        let two_to_three = Regex::matcher(Class::digit()).that_repeats(Times::new(2)) | Regex::matcher(Class::digit()).that_repeats(Times::new(3));
+        let digits = Regex::matcher(Class::digit()).that_repeats(ZeroToInfinity);
+
+        assert!(two_to_three.is_subset_of(&digits).unwrap().holds());
+        assert_eq!(Verdict::Counterexample(String::from("")), digits.is_subset_of(&two_to_three).unwrap())
+    }
+
+    #[test]
+    fn success_count_matches_of_length() {
+        let regex = Regex::from("id-") + Regex::matcher(Class::new(&[('a', 'f'), ('0', '9')])).that_repeats(ZeroToInfinity);
+
+        assert_eq!(MatchCount { count: Some(0), finite: false }, regex.count_matches_of_length(2).unwrap());
+        assert_eq!(Some(16 * 16), regex.count_matches_of_length(5).unwrap().count)
+    }
+
+    #[test]
+    fn success_count_matches_of_length_with_not() {
+        let regex = Regex::not(Regex::from("a"));
+
+        assert_eq!(Some(0x10FFFF + 1 - 0x800 - 1), regex.count_matches_of_length(1).unwrap().count);
+        assert!(regex.count_matches_of_length(7).unwrap().overflowed())
+    }
+
+    #[test]
+    fn failure_is_equivalent_backreference() {
+        let regex = Regex::group(Regex::from("a")) + Regex::backreference(1);
+
+        assert!(matches!(regex.is_equivalent(&Regex::from("aa")), Err(AutomatonError::Unsupported(_))))
+    }
+}
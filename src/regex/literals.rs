@@ -0,0 +1,237 @@
+use crate::regex::Regex;
+
+/// Literal text every match of a Regex must contain, found by looking at its structure.
+/// Search uses it to skip positions where no match can start.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Literals {
+    /// Every match is exactly this string.
+    pub exact: Option<String>,
+    /// Every match starts with this string. Empty if nothing is known.
+    pub prefix: String,
+    /// Every match ends with this string. Empty if nothing is known.
+    pub suffix: String,
+    /// Every match contains each of these strings.
+    pub inner: Vec<String>,
+}
+
+impl Literals {
+    /// Nothing is known about the matches.
+    pub fn unknown() -> Self {
+        Literals::default()
+    }
+
+    /// Every match is exactly the given string.
+    pub fn exact(string: &str) -> Self {
+        Literals {
+            exact: Some(String::from(string)),
+            prefix: String::from(string),
+            suffix: String::from(string),
+            inner: vec![String::from(string)],
+        }
+    }
+
+    /// Returns the longest string every match contains, if there is any.
+    pub fn longest_required(&self) -> Option<&str> {
+        self.inner.iter()
+            .chain([&self.prefix, &self.suffix])
+            .fold(None, |longest: Option<&String>, literal| match longest {
+                Some(longest) if longest.chars().count() >= literal.chars().count() => Some(longest),
+                _ => Some(literal)
+            })
+            .map(String::as_str)
+            .filter(|literal| !literal.is_empty())
+    }
+
+    /// Literals for matches of this followed by matches of the given next.
+    pub fn concat(self, next: Literals) -> Self {
+        if let (Some(own), Some(other)) = (&self.exact, &next.exact) {
+            return Literals::exact(&format!("{}{}", own, other));
+        }
+
+        let prefix = match &self.exact {
+            Some(own) => format!("{}{}", own, next.prefix),
+            None => self.prefix.clone()
+        };
+        let suffix = match &next.exact {
+            Some(other) => format!("{}{}", self.suffix, other),
+            None => next.suffix.clone()
+        };
+
+        let mut literals = Literals { exact: None, prefix, suffix, inner: self.inner };
+        literals.inner.extend(next.inner);
+        literals.add_inner(literals.prefix.clone());
+        literals.add_inner(literals.suffix.clone());
+        literals
+    }
+
+    /// Literals for matches of either this or the given other.
+    pub fn either(self, other: Literals) -> Self {
+        let exact = match self.exact == other.exact {
+            true => self.exact.clone(),
+            false => None
+        };
+        let prefix = common_prefix(&self.prefix, &other.prefix);
+        let suffix = common_suffix(&self.suffix, &other.suffix);
+
+        let mut literals = Literals { exact, prefix, suffix, inner: vec![] };
+        for literal in &self.inner {
+            if other.inner.contains(literal) {
+                literals.add_inner(literal.clone());
+            }
+        }
+        literals.add_inner(literals.prefix.clone());
+        literals.add_inner(literals.suffix.clone());
+        literals
+    }
+
+    /// Literals for matches that match both this and the given other.
+    pub fn both(self, other: Literals) -> Self {
+        let longer = |own: String, other: String| match own.chars().count() >= other.chars().count() {
+            true => own,
+            false => other
+        };
+
+        let mut literals = Literals {
+            exact: self.exact.or(other.exact),
+            prefix: longer(self.prefix, other.prefix),
+            suffix: longer(self.suffix, other.suffix),
+            inner: self.inner,
+        };
+        literals.inner.extend(other.inner);
+        literals
+    }
+
+    /// Literals for matches that repeat matches of this between min and max times.
+    pub fn repeated(self, min: usize, max: Option<usize>) -> Self {
+        if max == Some(0) || self.exact.as_deref() == Some("") {
+            return Literals::exact("");
+        }
+
+        match (min, &self.exact) {
+            (0, _) => Literals::unknown(),
+            (_, Some(exact)) if Some(min) == max => Literals::exact(&exact.repeat(min)),
+            (_, Some(exact)) => Literals {
+                exact: None,
+                prefix: exact.repeat(min),
+                suffix: exact.repeat(min),
+                inner: vec![exact.repeat(min)],
+            },
+            (_, None) => Literals { exact: None, ..self }
+        }
+    }
+
+    fn add_inner(&mut self, literal: String) {
+        if !literal.is_empty() && !self.inner.contains(&literal) {
+            self.inner.push(literal)
+        }
+    }
+}
+
+fn common_prefix(left: &str, right: &str) -> String {
+    left.chars().zip(right.chars())
+        .take_while(|(l, r)| l == r)
+        .map(|(l, _)| l)
+        .collect()
+}
+
+fn common_suffix(left: &str, right: &str) -> String {
+    let reversed: String = left.chars().rev().zip(right.chars().rev())
+        .take_while(|(l, r)| l == r)
+        .map(|(l, _)| l)
+        .collect();
+    reversed.chars().rev().collect()
+}
+
+impl<'a, T> Regex<'a, T> {
+    /// Returns the literal text every match of this Regex must contain.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::any::Any;
+    /// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+    ///
+    /// let regex = Regex::from("foo") + Regex::matcher(Any).that_repeats(ZeroToInfinity) + "bar";
+    /// let literals = regex.literals();
+    ///
+    /// assert_eq!("foo", literals.prefix);
+    /// assert_eq!("bar", literals.suffix);
+    /// ```
+    pub fn literals(&self) -> Literals {
+        let own_literals = match &self.repeat {
+            Some(repeat) => self.regex_type.literals().repeated(repeat.get_minimum().unwrap_or(0), repeat.get_maximum()),
+            None => self.regex_type.literals()
+        };
+
+        match &self.next {
+            Some(next_regex) => own_literals.concat(next_regex.literals()),
+            None => own_literals
+        }
+    }
+}
+
+/// Returns the char indexes of all places the literal starts at in the string, including overlapping ones.
+pub(crate) fn literal_positions(string: &str, literal: &str) -> Vec<usize> {
+    string.char_indices()
+        .enumerate()
+        .filter(|(_, (byte_index, _))| string[*byte_index..].starts_with(literal))
+        .map(|(char_index, _)| char_index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::regex::literals::{literal_positions, Literals};
+    use crate::regex::Regex;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    fn anything<'a>() -> Regex<'a> {
+        Regex::matcher(Any).that_repeats(ZeroToInfinity)
+    }
+
+    #[test]
+    fn success_literals_of_sequence() {
+        let literals = (Regex::from("foo") + "bar").literals();
+
+        assert_eq!(Literals::exact("foobar"), literals)
+    }
+
+    #[test]
+    fn success_literals_with_inner() {
+        let literals = (anything() + "foo" + anything() + "bar").literals();
+
+        assert_eq!(None, literals.exact);
+        assert_eq!("", literals.prefix);
+        assert_eq!("bar", literals.suffix);
+        assert_eq!(vec![String::from("foo"), String::from("bar")], literals.inner);
+        assert_eq!(Some("foo"), literals.longest_required())
+    }
+
+    #[test]
+    fn success_literals_of_or() {
+        let literals = ((Regex::from("foobar") | "fooxar") + anything()).literals();
+
+        assert_eq!("foo", literals.prefix);
+        assert_eq!("", literals.suffix)
+    }
+
+    #[test]
+    fn success_literals_of_repeat() {
+        assert_eq!(Literals::exact("abab"), (Regex::from("ab").that_repeats(Times::new(2))).literals());
+        assert_eq!(Literals::unknown(), (Regex::from("ab").that_repeats(ZeroToInfinity)).literals())
+    }
+
+    #[test]
+    fn success_literals_of_group_and_lookaround() {
+        let regex = Regex::lookahead(Regex::from("f")) + Regex::group(Regex::from("foo")) + anything();
+
+        assert_eq!("foo", regex.literals().prefix)
+    }
+
+    #[test]
+    fn success_literal_positions() {
+        assert_eq!(vec![0, 1, 4], literal_positions("aaaäaa", "aa"))
+    }
+}
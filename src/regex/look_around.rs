@@ -0,0 +1,166 @@
+use std::fmt::{Display, Formatter};
+
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
+
+/// The side of the current position a LookAround inspects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// The inner regex must match beginning at the current position.
+    Ahead,
+    /// The inner regex must match ending at the current position.
+    Behind,
+}
+
+/// A zero-width assertion: matches without consuming anything, if the inner regex
+/// matches next to the current position. A negative LookAround matches if it does not.
+pub struct LookAround<'a, T = char> {
+    direction: Direction,
+    negative: bool,
+    inner: Regex<'a, T>,
+}
+
+impl<'a, T> RegexType<'a, T> for LookAround<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let position = state.checkpoint();
+        let inner_matches = match self.negative {
+            true => {
+                let captures = state.capture_spans().to_vec();
+                let inner_matches = state.silenced(|state| state.exactly(|state| self.inner_matches(state, position)))?;
+                // Groups of the inner regex are no part of the match
+                state.restore_captures(&captures);
+                inner_matches
+            }
+            false => state.exactly(|state| self.inner_matches(state, position))?
+        };
+        state.backtrack(position);
+
+        match inner_matches != self.negative {
+            true => next(state),
+            false => Ok(false)
+        }
+    }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![&self.inner]
+    }
+
+    fn single_end(&self) -> bool {
+        true
+    }
+
+    fn literals(&self) -> Literals {
+        Literals::exact("")
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        let look_around = *self;
+        Regex::new_regex(LookAround::new(look_around.direction, look_around.negative, look_around.inner.simplified(stats)))
+    }
+}
+
+impl<'a, T> LookAround<'a, T> {
+    pub fn new(direction: Direction, negative: bool, inner: Regex<'a, T>) -> Self {
+        LookAround {
+            direction,
+            negative,
+            inner,
+        }
+    }
+
+    /// Returns if the inner regex matches next to the given position.
+    fn inner_matches(&self, state: &mut MatchState<T>, position: usize) -> Result<bool, MatchError> {
+        match self.direction {
+            Direction::Ahead => self.inner.matches_string(state, &mut |_| Ok(true)),
+            Direction::Behind => state.ignoring_more_input(|state| {
+                // Every start before the position is tried, the closest one first
+                for start in (0..=position).rev() {
+                    state.backtrack(start);
+                    if self.inner.matches_string(state, &mut |state| Ok(state.cursor.index() == position))? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            })
+        }
+    }
+}
+
+impl<'a, T> Display for LookAround<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let direction = match self.direction {
+            Direction::Ahead => "Lookahead",
+            Direction::Behind => "Lookbehind"
+        };
+        match self.negative {
+            true => write!(f, "Negative {}", direction),
+            false => write!(f, "{}", direction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::matcher::string::Str;
+    use crate::regex::Regex;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    fn anything<'a>() -> Regex<'a> {
+        Regex::matcher(Any).that_repeats(ZeroToInfinity)
+    }
+
+    #[test]
+    fn success_lookahead() {
+        let word = Regex::matcher(Str::new("key"));
+        let regex = word + Regex::lookahead(Regex::from(":")) + anything();
+
+        assert!(regex.matches("key: value"));
+        assert!(!regex.matches("key value"))
+    }
+
+    #[test]
+    fn success_negative_lookahead() {
+        let regex = Regex::negative_lookahead(Regex::from("foo")) + anything();
+
+        assert!(regex.matches("barfoo"));
+        assert!(!regex.matches("foobar"))
+    }
+
+    #[test]
+    fn failure_backreference_to_group_inside_negative_lookaround() {
+        let lookahead = (Regex::negative_lookahead(Regex::group(Regex::from("a"))) | "") + "a" + Regex::backreference(1);
+        let lookbehind = Regex::from("a") + (Regex::negative_lookbehind(Regex::group(Regex::from("a"))) | "") + Regex::backreference(1);
+
+        assert!(!lookahead.matches("aa"));
+        assert!(!lookbehind.matches("aa"))
+    }
+
+    #[test]
+    fn success_lookbehind() {
+        let regex = anything() + Regex::lookbehind(Regex::from("$")) + "5";
+
+        assert!(regex.matches("costs $5"));
+        assert!(!regex.matches("costs 5"))
+    }
+
+    #[test]
+    fn success_negative_lookbehind() {
+        let price = Regex::negative_lookbehind(Regex::from("-")) + "5";
+        let regex = anything() + price;
+
+        assert!(regex.matches("price: 5"));
+        assert!(!regex.matches("price: -5"))
+    }
+
+    #[test]
+    fn success_lookbehind_at_the_start() {
+        let regex = Regex::negative_lookbehind(Regex::matcher(Any)) + "foo";
+
+        assert!(regex.matches("foo"))
+    }
+}
@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::cursor::{Cursor, TokenCursor};
+use crate::regex::approximate::EditCosts;
+use crate::regex::backreference::GroupReference;
+use crate::regex::captures::Captures;
+use crate::regex::completion::Completion;
+use crate::regex::diagnosis::Diagnosis;
+use crate::regex::group::Group;
+use crate::regex::observer::{MatchObserver, NodeId};
+use crate::regex::outcome::{Limit, MatchError};
+use crate::regex::Regex;
+use crate::regex::rules::RuleSet;
+use crate::string_pointer::StringPointer;
+
+/// Everything a matching attempt needs to keep track of, besides the regex itself.
+/// T is the type of the tokens the regex matches, which is char for text.
+pub struct MatchState<'o, 'r, T = char> {
+    pub cursor: Box<dyn Cursor<T> + 'o>,
+    depth: usize,
+    recursion_limit: usize,
+    furthest_failure: usize,
+    expected: Vec<String>,
+    silenced: usize,
+    more_input_expected: bool,
+    completions: Option<Vec<Completion>>,
+    approximation: Option<Approximation>,
+    limits: Option<Limits<'o>>,
+    observation: Option<Observation<'o>>,
+    groups: Rc<GroupNumbering>,
+    captures: Vec<Option<(usize, usize)>>,
+    rules: Option<&'o RuleSet<'r>>,
+}
+
+/// The numbers and names of the groups a MatchState captures with, computed once
+/// for a regex, so it can be set again without looking for its groups.
+#[derive(Default)]
+pub struct GroupNumbering {
+    numbers: HashMap<*const (), usize>,
+    names: Vec<Option<String>>,
+}
+
+impl GroupNumbering {
+    /// Numbers the given groups starting with 1.
+    pub fn of<T>(groups: Vec<&Group<T>>) -> Self {
+        let mut names = vec![None];
+        names.extend(groups.iter().map(|group| group.name().map(String::from)));
+        GroupNumbering {
+            numbers: groups.iter().enumerate().map(|(index, group)| (group_key(group), index + 1)).collect(),
+            names,
+        }
+    }
+}
+
+/// An observer, together with what is needed to tell it which node does what.
+struct Observation<'o> {
+    observer: &'o mut dyn MatchObserver,
+    node_ids: HashMap<*const (), NodeId>,
+    entered_nodes: Vec<NodeId>,
+}
+
+/// How many steps the deadline and the cancellation flag are checked after.
+const LIMIT_CHECK_INTERVAL: usize = 256;
+
+/// The limits a matching attempt has to stay within, and how many steps it took so far.
+struct Limits<'o> {
+    max_steps: usize,
+    steps: usize,
+    deadline: Option<Instant>,
+    cancelled: &'o AtomicBool,
+}
+
+impl<'o> Limits<'o> {
+    /// Counts one step. Fails if that exceeds a limit.
+    fn step(&mut self) -> Result<(), MatchError> {
+        if self.steps == self.max_steps {
+            return Err(MatchError::LimitExceeded(Limit::Steps(self.max_steps)));
+        }
+
+        if self.steps.is_multiple_of(LIMIT_CHECK_INTERVAL) {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return Err(MatchError::LimitExceeded(Limit::Cancelled));
+            }
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(MatchError::LimitExceeded(Limit::Deadline));
+            }
+        }
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+/// What approximate matching allows to edit, and how much of it was used.
+struct Approximation {
+    costs: EditCosts,
+    max_cost: usize,
+    spent: usize,
+    start: usize,
+    suspended: usize,
+}
+
+impl<'o, 'r, T> MatchState<'o, 'r, T> {
+    pub fn new(string: &str, recursion_limit: usize) -> Self {
+        MatchState::with_cursor(Box::new(StringPointer::from(string)), recursion_limit)
+    }
+
+    /// Creates a MatchState for matching the given tokens.
+    pub fn for_tokens(tokens: &'o [T], recursion_limit: usize) -> Self where T: PartialEq {
+        MatchState::with_cursor(Box::new(TokenCursor::new(tokens)), recursion_limit)
+    }
+
+    /// Creates a MatchState that matches at the positions of the given cursor.
+    pub fn with_cursor(cursor: Box<dyn Cursor<T> + 'o>, recursion_limit: usize) -> Self {
+        let mut state = MatchState {
+            cursor,
+            depth: 0,
+            recursion_limit,
+            furthest_failure: 0,
+            expected: vec![],
+            silenced: 0,
+            more_input_expected: false,
+            completions: None,
+            approximation: None,
+            limits: None,
+            observation: None,
+            groups: Rc::default(),
+            captures: vec![],
+            rules: None,
+        };
+        state.set_groups(vec![]);
+        state
+    }
+
+    /// Numbers the groups of the given root regex, so they can capture.
+    pub fn with_groups_of(mut self, root: &Regex<T>) -> Self {
+        self.set_groups(root.groups());
+        self
+    }
+
+    /// Numbers the given groups starting with 1 and forgets everything captured so far.
+    pub fn set_groups(&mut self, groups: Vec<&Group<T>>) {
+        self.set_group_numbering(Rc::new(GroupNumbering::of(groups)))
+    }
+
+    /// Captures with the given numbering from now on and forgets everything captured so far.
+    pub fn set_group_numbering(&mut self, groups: Rc<GroupNumbering>) {
+        self.captures = vec![None; groups.names.len()];
+        self.groups = groups;
+    }
+
+    /// Forgets everything captured so far, but keeps the numbered groups.
+    pub fn clear_captures(&mut self) {
+        self.captures.fill(None)
+    }
+
+    /// Stops matching with an error after the given number of steps, at the given deadline
+    /// or once the given flag is set. Every regex matching enters counts as a step.
+    pub fn with_limits(mut self, max_steps: usize, deadline: Option<Instant>, cancelled: &'o AtomicBool) -> Self {
+        self.limits = Some(Limits {
+            max_steps,
+            steps: 0,
+            deadline,
+            cancelled,
+        });
+        self
+    }
+
+    /// Lets the given observer watch every step of matching the given root regex.
+    pub fn observed_by(mut self, observer: &'o mut dyn MatchObserver, root: &Regex<T>) -> Self {
+        let nodes = root.nodes();
+        let labels: Vec<String> = nodes.iter().map(|node| node.label()).collect();
+        observer.nodes(&labels);
+
+        self.observation = Some(Observation {
+            observer,
+            node_ids: nodes.into_iter().enumerate().map(|(id, node)| (node_key(node), id)).collect(),
+            entered_nodes: vec![],
+        });
+        self
+    }
+
+    /// Marks that matching started on the given regex. Fails if this exceeds the recursion limit.
+    pub fn enter_node(&mut self, regex: &Regex<T>) -> Result<(), MatchError> {
+        self.enter()?;
+
+        let position = self.cursor.index();
+        if let Some(observation) = &mut self.observation {
+            let node = observation.node_ids.get(&node_key(regex)).copied().unwrap_or_default();
+            observation.entered_nodes.push(node);
+            observation.observer.enter(node, position);
+        }
+        Ok(())
+    }
+
+    /// Marks that matching the regex entered last is done.
+    pub fn exit_node(&mut self, matched: bool) {
+        self.exit();
+
+        let position = self.cursor.index();
+        if let Some(observation) = &mut self.observation {
+            if let Some(node) = observation.entered_nodes.pop() {
+                observation.observer.exit(node, position, matched)
+            }
+        }
+    }
+
+    /// Returns the current index, which the current node might want to return to later.
+    pub fn checkpoint(&mut self) -> usize {
+        let position = self.cursor.index();
+        if let Some(observation) = &mut self.observation {
+            if let Some(node) = observation.entered_nodes.last() {
+                observation.observer.checkpoint(*node, position)
+            }
+        }
+        position
+    }
+
+    /// Returns to an index remembered with checkpoint before.
+    pub fn backtrack(&mut self, position: usize) {
+        let from = self.cursor.index();
+        if let Some(observation) = &mut self.observation {
+            if let Some(node) = observation.entered_nodes.last() {
+                observation.observer.backtrack(*node, from, position)
+            }
+        }
+        self.cursor.set_index(position)
+    }
+
+    /// Marks that matching went one regex deeper. Fails if this exceeds the recursion limit
+    /// or one of the limits set with with_limits.
+    pub fn enter(&mut self) -> Result<(), MatchError> {
+        if self.depth == self.recursion_limit {
+            return Err(MatchError::RecursionLimitExceeded(self.recursion_limit));
+        }
+        self.step()?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Counts a step of matching that does not go deeper. Fails if this exceeds one of the limits
+    /// set with with_limits.
+    pub fn step(&mut self) -> Result<(), MatchError> {
+        match &mut self.limits {
+            Some(limits) => limits.step(),
+            None => Ok(())
+        }
+    }
+
+    /// Marks that matching returned from a regex entered before.
+    pub fn exit(&mut self) {
+        self.depth = self.depth.saturating_sub(1)
+    }
+
+    /// Returns the number of the given group.
+    pub fn group_index(&self, group: &Group<T>) -> Result<usize, MatchError> {
+        self.groups.numbers.get(&group_key(group))
+            .copied()
+            .ok_or_else(|| MatchError::UnknownGroup(group.to_string()))
+    }
+
+    /// Sets the span captured by the group with the given number and returns the former one.
+    pub fn set_capture(&mut self, index: usize, span: Option<(usize, usize)>) -> Option<(usize, usize)> {
+        std::mem::replace(&mut self.captures[index], span)
+    }
+
+    /// Returns the span captured by the referenced group, or None if it did not capture anything yet.
+    pub fn captured_span(&self, reference: &GroupReference) -> Result<Option<(usize, usize)>, MatchError> {
+        let index = match reference {
+            GroupReference::Number(number) if *number > 0 && *number < self.captures.len() => *number,
+            GroupReference::Name(name) => self.groups.names.iter()
+                .position(|group_name| group_name.as_deref() == Some(name))
+                .ok_or_else(|| MatchError::UnknownGroup(reference.to_string()))?,
+            _ => return Err(MatchError::UnknownGroup(reference.to_string()))
+        };
+
+        Ok(self.captures[index])
+    }
+
+    /// Creates Captures from the spans captured so far, with the whole string as group 0.
+    pub fn captures(&self, string: &str) -> Captures {
+        self.captures_of(string, self.captures.clone(), (0, self.cursor.len()))
+    }
+
+    /// Returns the spans captured so far, indexed by the number of their group.
+    pub fn capture_spans(&self) -> &[Option<(usize, usize)>] {
+        &self.captures
+    }
+
+    /// Replaces the spans captured so far with the given ones, taken from capture_spans before.
+    pub fn restore_captures(&mut self, spans: &[Option<(usize, usize)>]) {
+        self.captures.copy_from_slice(spans)
+    }
+
+    /// Creates Captures from the given spans, with the given span of the whole match as group 0.
+    pub fn captures_of(&self, string: &str, mut spans: Vec<Option<(usize, usize)>>, span: (usize, usize)) -> Captures {
+        spans[0] = Some(span);
+        Captures::new(string, spans, self.groups.names.clone())
+    }
+
+    /// Records that something described by the given function was expected at the given index,
+    /// but not found. Only the expectations at the furthest index are kept.
+    pub fn expect(&mut self, index: usize, description: impl FnOnce() -> String) {
+        if self.silenced > 0 || index < self.furthest_failure {
+            return;
+        }
+
+        if index > self.furthest_failure {
+            self.furthest_failure = index;
+            self.expected.clear();
+        }
+
+        let description = description();
+        if !self.expected.contains(&description) {
+            self.expected.push(description)
+        }
+    }
+
+    /// Runs the given function without recording expectations. Used where a failing
+    /// match does not mean the whole regex failed, like inside a Not.
+    pub fn silenced<R>(&mut self, function: impl FnOnce(&mut Self) -> R) -> R {
+        self.silence();
+        let result = function(self);
+        self.unsilence();
+        result
+    }
+
+    /// Stops recording expectations, until unsilence is called as often as this.
+    pub fn silence(&mut self) {
+        self.silenced += 1
+    }
+
+    /// Undoes one call of silence.
+    pub fn unsilence(&mut self) {
+        self.silenced = self.silenced.saturating_sub(1)
+    }
+
+    /// Returns if expectations are not recorded at the moment.
+    pub fn is_silenced(&self) -> bool {
+        self.silenced > 0
+    }
+
+    /// Records that a regex reached the end of the input, but could have matched
+    /// if more had followed. The given function tells what could have followed, if known.
+    pub fn expect_more_input(&mut self, completion: impl FnOnce() -> Option<Completion>) {
+        self.more_input_expected = true;
+        if let (Some(completions), Some(completion)) = (&mut self.completions, completion()) {
+            if !completions.contains(&completion) {
+                completions.push(completion)
+            }
+        }
+    }
+
+    /// Returns if any regex reached the end of the input wanting more so far.
+    pub fn more_input_expected(&self) -> bool {
+        self.more_input_expected
+    }
+
+    /// Makes expect_more_input collect what could follow the input.
+    pub fn collecting_completions(mut self) -> Self {
+        self.completions = Some(vec![]);
+        self
+    }
+
+    /// Returns what could follow the input, as collected so far.
+    pub fn completions(&self) -> &[Completion] {
+        self.completions.as_deref().unwrap_or_default()
+    }
+
+    /// Runs the given function without recording that more input was expected. Used where
+    /// reading past the position does not depend on the end of the input, like in a lookbehind.
+    pub fn ignoring_more_input<R>(&mut self, function: impl FnOnce(&mut Self) -> R) -> R {
+        let more_input_expected = self.more_input_expected;
+        let completions = self.completions.as_ref().map(Vec::len);
+        let result = function(self);
+
+        self.more_input_expected = more_input_expected;
+        if let (Some(all_completions), Some(length)) = (&mut self.completions, completions) {
+            all_completions.truncate(length);
+        }
+        result
+    }
+
+    /// Lets regexes edit the input while matching, as long as the edits cost at most
+    /// the given maximum in total.
+    pub fn approximating(mut self, costs: EditCosts, max_cost: usize) -> Self {
+        self.approximation = Some(Approximation {
+            costs,
+            max_cost,
+            spent: 0,
+            start: 0,
+            suspended: 0,
+        });
+        self
+    }
+
+    /// Starts an approximate match at the given index anew, which may cost at most the given maximum.
+    pub fn approximate_from(&mut self, start: usize, max_cost: usize) {
+        if let Some(approximation) = &mut self.approximation {
+            approximation.max_cost = max_cost;
+            approximation.spent = 0;
+            approximation.start = start;
+        }
+    }
+
+    /// Lowers the maximum cost of the current approximate match to the given one.
+    pub fn limit_edit_cost(&mut self, max_cost: usize) {
+        if let Some(approximation) = &mut self.approximation {
+            approximation.max_cost = max_cost;
+        }
+    }
+
+    /// Returns the costs of edits, or None if the input may not be edited right now.
+    pub fn edit_costs(&self) -> Option<EditCosts> {
+        self.approximation.as_ref()
+            .filter(|approximation| approximation.suspended == 0)
+            .map(|approximation| approximation.costs)
+    }
+
+    /// Returns what the edits made so far cost.
+    pub fn edit_cost(&self) -> usize {
+        self.approximation.as_ref().map_or(0, |approximation| approximation.spent)
+    }
+
+    /// Returns the index the current approximate match started at.
+    pub fn approximation_start(&self) -> Option<usize> {
+        self.approximation.as_ref().map(|approximation| approximation.start)
+    }
+
+    /// Runs the given function after an edit of the given cost, unless that exceeds the maximum cost.
+    pub fn edited(&mut self, cost: usize, function: impl FnOnce(&mut Self) -> Result<bool, MatchError>) -> Result<bool, MatchError> {
+        match &mut self.approximation {
+            Some(approximation) if approximation.spent + cost <= approximation.max_cost => approximation.spent += cost,
+            _ => return Ok(false)
+        }
+
+        let result = function(self);
+        if let Some(approximation) = &mut self.approximation {
+            approximation.spent -= cost;
+        }
+        result
+    }
+
+    /// Runs the given function without allowing edits. Used where a match is only
+    /// checked and not kept, like in a lookaround, so edits there would cost nothing.
+    pub fn exactly<R>(&mut self, function: impl FnOnce(&mut Self) -> R) -> R {
+        if let Some(approximation) = &mut self.approximation {
+            approximation.suspended += 1;
+        }
+        let result = function(self);
+        if let Some(approximation) = &mut self.approximation {
+            approximation.suspended -= 1;
+        }
+        result
+    }
+
+    /// Creates a Diagnosis from the expectations recorded so far.
+    pub fn diagnosis(&self) -> Diagnosis {
+        Diagnosis {
+            position: self.furthest_failure,
+            expected: self.expected.clone(),
+            found: self.cursor.text(self.furthest_failure, self.furthest_failure + 1).and_then(|text| text.chars().next()),
+        }
+    }
+}
+
+impl<'o, 'r> MatchState<'o, 'r> {
+    /// Makes the rules of the given RuleSet available to rule references.
+    /// The groups of all rules are numbered in the order the rules were defined.
+    pub fn with_rules(mut self, rules: &'o RuleSet<'r>) -> Self {
+        self.set_groups(rules.groups());
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Returns the rule with the given name.
+    pub fn rule(&self, name: &str) -> Result<&'o Regex<'r>, MatchError> {
+        self.rules
+            .and_then(|rules| rules.get(name))
+            .ok_or_else(|| MatchError::UnknownRule(String::from(name)))
+    }
+}
+
+fn node_key<T>(regex: &Regex<T>) -> *const () {
+    regex as *const Regex<T> as *const ()
+}
+
+fn group_key<T>(group: &Group<T>) -> *const () {
+    group as *const Group<T> as *const ()
+}
@@ -1,20 +1,104 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
 use crate::matcher::Matcher;
-use crate::regex::regex_type::RegexType;
-use crate::string_pointer::{StringPointer, StringPointerError};
+use crate::regex::approximate::EditCosts;
+use crate::regex::completion::Completion;
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::Regex;
+use crate::regex::simplify::SimplifyStats;
 
 pub struct Match<M: Matcher> {
     matcher: M
 }
 
-impl<M: Matcher> RegexType for Match<M> {
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
+impl<'a, T: 'a, M: Matcher + 'a> RegexType<'a, T> for Match<M> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
         let checked_string_length = self.matcher.checked_string_length();
-        match string_pointer.take_next(checked_string_length) {
-            Ok(string) => Ok(self.matcher.matches(string)),
-            Err(error) => Err(error)
+        if checked_string_length > 0 && state.edit_costs().is_some() {
+            return self.matches_edited(state, next);
         }
+
+        let start = state.cursor.index();
+        let matches = match state.cursor.take_text(checked_string_length) {
+            Some(string) => self.matcher.matches(string),
+            None => {
+                if let Some(rest) = state.cursor.text(start, state.cursor.len()) {
+                    if self.matcher.could_start_with(&rest) {
+                        state.expect_more_input(|| self.completion_after(&rest));
+                    }
+                }
+                false
+            }
+        };
+
+        match matches {
+            true => next(state),
+            false => {
+                state.expect(start, || self.matcher.description());
+                Ok(false)
+            }
+        }
+    }
+
+    fn nullable(&self, _nullable_rules: &HashMap<String, bool>) -> bool {
+        self.matcher.checked_string_length() == 0
+    }
+
+    fn leading_rules(&self, _nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        vec![]
+    }
+
+    fn literals(&self) -> Literals {
+        match self.matcher.literal() {
+            Some(literal) => Literals::exact(&literal),
+            None => Literals::unknown()
+        }
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        if let Some(chars) = self.matcher.chars() {
+            return Ok(nfa.atoms(alphabet.atoms_of(&chars)));
+        }
+
+        let literal = self.matcher.literal().ok_or_else(|| AutomatonError::Unsupported(self.to_string()))?;
+        let empty = nfa.empty();
+        Ok(literal.chars().fold(empty, |fragment, c| {
+            let next = nfa.atoms(vec![alphabet.atom_of(c)]);
+            nfa.concat(fragment, next)
+        }))
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        match (self.matcher.chars(), self.matcher.literal()) {
+            (Some(chars), _) => output.push(generator.random_char(&chars)),
+            (None, Some(literal)) => output.push_str(&literal),
+            (None, None) => return Err(GenerateError::Unsupported(self.to_string()))
+        }
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, _stats: &mut SimplifyStats) -> Regex<'a, T> {
+        Regex::new_regex(*self)
+    }
+
+    fn literal(&self) -> Option<String> {
+        self.matcher.literal()
+    }
+
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        self.matcher.chars()
+    }
+
+    fn single_end(&self) -> bool {
+        true
     }
 }
 
@@ -24,10 +108,70 @@ impl<M: Matcher> Match<M> {
             matcher
         }
     }
+
+    /// Matches the matcher, allowing edits of the costs the given MatchState has.
+    fn matches_edited<T>(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let costs = state.edit_costs().unwrap_or_default();
+        let literal: Option<Vec<char>> = self.matcher.literal().map(|literal| literal.chars().collect());
+        self.matches_approximately(state, costs, literal.as_deref(), 0, next)
+    }
+
+    /// Matches the given literal of the matcher from the char with the given index on, allowing
+    /// edits. Without a literal, the matcher is edited as a whole, which costs as much as
+    /// editing each of its chars.
+    fn matches_approximately<T>(&self, state: &mut MatchState<T>, costs: EditCosts, literal: Option<&[char]>, unit: usize, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let (units, width) = match literal {
+            Some(chars) => (chars.len(), 1),
+            None => (1, self.matcher.checked_string_length())
+        };
+        if unit == units {
+            return next(state);
+        }
+
+        state.enter()?;
+        let position = state.cursor.index();
+        let mut matches = false;
+
+        // The chars as they are, or substituted
+        if let Some(text) = state.cursor.take_text(width) {
+            let unchanged = match literal {
+                Some(chars) => text.starts_with(chars[unit]),
+                None => self.matcher.matches(text)
+            };
+            let cost = if unchanged { 0 } else { costs.substitution * width };
+            matches = state.edited(cost, |state| self.matches_approximately(state, costs, literal, unit + 1, next))?;
+        }
+
+        // A char inserted before them, which is pointless at the start of the match
+        if !matches && state.approximation_start() != Some(position) {
+            state.cursor.set_index(position);
+            if state.cursor.take_text(1).is_some() {
+                matches = state.edited(costs.insertion, |state| self.matches_approximately(state, costs, literal, unit, next))?;
+            }
+        }
+
+        // The chars deleted
+        if !matches {
+            state.cursor.set_index(position);
+            matches = state.edited(costs.deletion * width, |state| self.matches_approximately(state, costs, literal, unit + 1, next))?;
+        }
+
+        state.exit();
+        Ok(matches)
+    }
+
+    /// Returns what could follow the given rest of the input, to complete a match of the matcher.
+    fn completion_after(&self, rest: &str) -> Option<Completion> {
+        match (self.matcher.literal(), self.matcher.chars()) {
+            (Some(literal), _) => Some(Completion::Literal(literal.chars().skip(rest.chars().count()).collect())),
+            (None, Some(chars)) if rest.is_empty() => Some(Completion::Chars(chars)),
+            _ => None
+        }
+    }
 }
 
 impl<M: Matcher> Display for Match<M> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Match")
+        write!(f, "Match {}", self.matcher.description())
     }
-}
\ No newline at end of file
+}
@@ -1,55 +1,226 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
+use crate::matcher::class::Class;
+use crate::matcher::fn_matcher::FnMatcher;
 use crate::matcher::Matcher;
 use crate::regex::and::And;
+use crate::regex::backreference::{Backreference, GroupReference};
+use crate::regex::captures::Captures;
+use crate::regex::diagnosis::Diagnosis;
+use crate::regex::group::Group;
+use crate::regex::literals::literal_positions;
+use crate::regex::look_around::{Direction, LookAround};
+use crate::regex::match_state::MatchState;
 use crate::regex::matcher::Match;
 use crate::regex::not::Not;
+use crate::regex::observer::MatchObserver;
 use crate::regex::or::Or;
-use crate::regex::regex_type::RegexType;
+use crate::regex::outcome::{MatchError, MatchOutcome, PrefixMatch};
+use crate::regex::predicate::Predicate;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::rule_reference::RuleReference;
+use crate::regex::sequence::Sequence;
 use crate::repeat::Repeat;
-use crate::string_pointer::{StringPointer, StringPointerError};
 
+pub mod outcome;
+pub mod diagnosis;
+pub mod observer;
+pub mod captures;
+pub mod rules;
+pub(crate) mod rule_check;
+pub mod literals;
+pub mod simplify;
+mod language;
+mod posix;
+pub mod generator;
+pub mod pattern;
+pub mod extract;
+pub mod completion;
+pub mod approximate;
 mod regex_type;
+pub(crate) mod match_state;
 mod matcher;
 mod and;
 mod or;
 mod not;
+mod group;
+mod backreference;
+mod look_around;
+mod rule_reference;
+mod predicate;
+mod sequence;
+mod ops;
 
-pub struct Regex<'a> {
-    regex_type: Box<dyn RegexType + 'a>,
-    next: Option<Box<Regex<'a>>>,
+/// How deep regexes may nest while matching, before the attempt is aborted, unless set with
+/// Regex::with_recursion_limit. Every regex in a chain counts as one level, and so does every
+/// repeat of a regex that can end at several positions.
+pub const DEFAULT_RECURSION_LIMIT: usize = 2_000;
+
+/// How many bytes remembering where the rest of a repeat failed may take, during one attempt
+/// to match the repeat. Beyond it, matching backtracks without remembering.
+pub const DEFAULT_MEMO_BUDGET: usize = 1 << 20;
+
+/// A regex over tokens of type T. Regexes over text use char, which is the default.
+pub struct Regex<'a, T: 'a = char> {
+    regex_type: Box<dyn RegexType<'a, T> + 'a>,
+    next: Option<Box<Regex<'a, T>>>,
     repeat: Option<Box<dyn Repeat + 'a>>,
+    recursion_limit: usize,
 }
 
-impl<'a> Regex<'a> {
+/// The spans captured by the groups of a Regex, indexed by group number.
+type Spans = Vec<Option<(usize, usize)>>;
+
+/// Where the rest of a repeat without a maximum failed before, during one attempt to match it.
+/// Past the minimum, the rest always gets the same continuation, so whether it matches only
+/// depends on the position and on what groups captured so far. Edits of approximate matching
+/// add to that, so no failures are remembered then.
+struct RepeatFailures {
+    enabled: bool,
+    failures: HashMap<usize, HashSet<Spans>>,
+    bytes: usize,
+}
+
+impl RepeatFailures {
+    fn new(enabled: bool) -> Self {
+        RepeatFailures {
+            enabled,
+            failures: HashMap::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Returns if the rest failed before at the current position, with the same captures.
+    fn failed_before<T>(&self, state: &MatchState<T>) -> bool {
+        self.enabled && self.failures.get(&state.cursor.index())
+            .is_some_and(|captures| captures.contains(state.capture_spans()))
+    }
+
+    /// Remembers that the rest failed at the given position with the given captures,
+    /// unless that exceeds DEFAULT_MEMO_BUDGET.
+    fn remember(&mut self, position: usize, spans: &[Option<(usize, usize)>]) {
+        // The spans, and their slot in the set, and the slot of a new position in the map
+        let mut bytes = std::mem::size_of_val(spans) + table_slot_bytes::<Spans>();
+        if !self.failures.contains_key(&position) {
+            bytes += table_slot_bytes::<(usize, HashSet<Spans>)>();
+        }
+        if !self.enabled || self.bytes + bytes > DEFAULT_MEMO_BUDGET {
+            return;
+        }
+        self.bytes += bytes;
+        self.failures.entry(position).or_default().insert(spans.to_vec());
+    }
+}
+
+/// Returns about how many bytes an entry of type E takes in a HashMap or HashSet,
+/// which keeps a control byte for each slot and up to an eighth of its slots free.
+fn table_slot_bytes<E>() -> usize {
+    (std::mem::size_of::<E>() + 1) * 8 / 7 + 1
+}
+
+impl<'a, T> Regex<'a, T> {
     pub fn matcher(matcher: impl Matcher + 'a) -> Self {
         Self::new_regex(Match::new(matcher))
     }
 
-    pub fn and(left: Regex<'a>, right: Regex<'a>) -> Self {
+    pub fn and(left: Regex<'a, T>, right: Regex<'a, T>) -> Self {
         Self::new_regex(And::new(left, right))
     }
 
-    pub fn or(left: Regex<'a>, right: Regex<'a>) -> Self {
+    pub fn or(left: Regex<'a, T>, right: Regex<'a, T>) -> Self {
         Self::new_regex(Or::new(left, right))
     }
 
-    pub fn not(inner: Regex<'a>) -> Self {
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(inner: Regex<'a, T>) -> Self {
         Self::new_regex(Not::new(inner))
     }
 
-    fn new_regex(regex_type: impl RegexType + 'a) -> Self {
+    /// Creates a group that captures the part of the string the inner regex matches.
+    /// Groups are numbered from left to right, starting with 1.
+    pub fn group(inner: Regex<'a, T>) -> Self {
+        Self::new_regex(Group::new(None, inner))
+    }
+
+    /// Creates a group like Regex::group, which can also be referred to by the given name.
+    pub fn named_group(name: &str, inner: Regex<'a, T>) -> Self {
+        Self::new_regex(Group::new(Some(name), inner))
+    }
+
+    /// Creates a regex that matches exactly the text the group with the given number captured before.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::any::Any;
+    /// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+    ///
+    /// let word = Regex::matcher(Any).followed_by(Regex::matcher(Any).that_repeats(ZeroToInfinity));
+    /// let doubled_word = Regex::group(word) + " " + Regex::backreference(1);
+    ///
+    /// assert!(doubled_word.matches("hello hello"));
+    /// assert!(!doubled_word.matches("hello world"));
+    /// ```
+    pub fn backreference(number: usize) -> Self {
+        Self::new_regex(Backreference::new(GroupReference::Number(number)))
+    }
+
+    /// Creates a regex that matches exactly the text the group with the given name captured before.
+    pub fn named_backreference(name: &str) -> Self {
+        Self::new_regex(Backreference::new(GroupReference::Name(String::from(name))))
+    }
+
+    /// Creates a regex that matches without consuming anything, if the inner regex
+    /// matches beginning at the current position.
+    pub fn lookahead(inner: Regex<'a, T>) -> Self {
+        Self::new_regex(LookAround::new(Direction::Ahead, false, inner))
+    }
+
+    /// Creates a regex that matches without consuming anything, if the inner regex
+    /// does not match beginning at the current position.
+    pub fn negative_lookahead(inner: Regex<'a, T>) -> Self {
+        Self::new_regex(LookAround::new(Direction::Ahead, true, inner))
+    }
+
+    /// Creates a regex that matches without consuming anything, if the inner regex
+    /// matches ending at the current position.
+    pub fn lookbehind(inner: Regex<'a, T>) -> Self {
+        Self::new_regex(LookAround::new(Direction::Behind, false, inner))
+    }
+
+    /// Creates a regex that matches without consuming anything, if the inner regex
+    /// does not match ending at the current position.
+    pub fn negative_lookbehind(inner: Regex<'a, T>) -> Self {
+        Self::new_regex(LookAround::new(Direction::Behind, true, inner))
+    }
+
+    /// Creates a regex that matches a single token the given predicate accepts.
+    /// It only matches slices of tokens, text is matched by matchers instead.
+    pub fn token(predicate: impl Fn(&T) -> bool + 'a) -> Self {
+        Self::new_regex(Predicate::new(predicate))
+    }
+
+    /// Creates a regex that matches a single token the given FnMatcher accepts.
+    pub fn token_matcher<F: Fn(T) -> bool + 'a>(matcher: FnMatcher<F, T>) -> Self where T: Clone + 'a {
+        Self::token(move |token: &T| matcher.accepts(token.clone()))
+    }
+
+    fn new_regex(regex_type: impl RegexType<'a, T> + 'a) -> Self {
         Regex {
             regex_type: Box::new(regex_type),
             next: None,
             repeat: None,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
         }
     }
 
     /// This method is used to chain multiple regexes together.
     /// The given next regex is always appended to the last
-    /// element in the chain.
+    /// element in the chain. Finding that element takes as long as the chain is,
+    /// except for a Regex created with Regex::sequence, where appending takes constant time.
     ///
     /// Examples:
     /// ```
@@ -72,103 +243,347 @@ impl<'a> Regex<'a> {
     /// assert!(regex_one.matches("foo bar baz"));
     /// assert!(regex_two.matches("foo bar baz"));
     /// ```
-    pub fn followed_by(mut self, next: Regex<'a>) -> Self {
-        match self.next {
-            Some(next_regex) => Regex {
-                regex_type: self.regex_type,
-                next: Some(Box::new(next_regex.followed_by(next))),
-                repeat: self.repeat,
-            },
-            None => {
-                self.next = Some(Box::new(next));
-                self
+    pub fn followed_by(mut self, next: Regex<'a, T>) -> Self {
+        if self.repeat.is_none() && self.next.is_none() {
+            if let Some(sequence) = self.regex_type.sequence_mut() {
+                sequence.push(next);
+                return self;
+            }
+        }
+
+        let mut last = &mut self.next;
+        while let Some(next_regex) = last {
+            last = &mut next_regex.next;
+        }
+        *last = Some(Box::new(next));
+        self
+    }
+
+    /// Creates a Regex that matches the given regexes one after another. Unlike chaining
+    /// them with followed_by, this takes linear time, and appending to the result with
+    /// followed_by or `+` takes constant time. No regexes match the empty string.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::sequence(vec!["foo", "-", "bar"].into_iter().map(Regex::from));
+    ///
+    /// assert!(regex.matches("foo-bar"));
+    /// assert!((regex + "!").matches("foo-bar!"));
+    /// ```
+    pub fn sequence(regexes: impl IntoIterator<Item = Regex<'a, T>>) -> Self {
+        Self::sequence_of(regexes.into_iter().collect())
+    }
+
+    /// Creates a Regex that matches any of the given regexes, trying them in the given order.
+    /// The alternatives are nested as a balanced tree, so thousands of them stay shallow.
+    /// No regexes match nothing at all.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::any_of(vec!["red", "green", "blue"].into_iter().map(Regex::from));
+    ///
+    /// assert!(regex.matches("green"));
+    /// assert!(!regex.matches("yellow"));
+    /// ```
+    pub fn any_of(regexes: impl IntoIterator<Item = Regex<'a, T>>) -> Self {
+        let mut regexes: Vec<Regex<'a, T>> = regexes.into_iter().collect();
+        if regexes.is_empty() {
+            return Regex::matcher(Class::new(&[]));
+        }
+
+        while regexes.len() > 1 {
+            let mut pairs = Vec::with_capacity(regexes.len() / 2 + 1);
+            let mut regexes_left = regexes.into_iter();
+            while let Some(left) = regexes_left.next() {
+                pairs.push(match regexes_left.next() {
+                    Some(right) => Regex::or(left, right),
+                    None => left
+                });
             }
+            regexes = pairs;
         }
+        regexes.remove(0)
     }
 
+    /// Sets how often this Regex should repeat. If regexes follow this one, only this one
+    /// repeats. Chains built with `+` are sequences, which repeat as a whole.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::repeat::times::Times;
+    ///
+    /// let regex = (Regex::from("foo") + "bar").that_repeats(Times::new(2));
+    ///
+    /// assert!(regex.matches("foobarfoobar"));
+    /// assert!(!regex.matches("foofoobar"));
+    /// ```
     pub fn that_repeats(mut self, repeat: impl Repeat + 'a) -> Self {
         self.repeat = Some(Box::new(repeat));
         self
     }
 
-    pub fn matches(&self, string: &str) -> bool {
-        let mut string_pointer = StringPointer::from(string);
-        let match_result = self.matches_string(&mut string_pointer);
+    /// Sets how deep matching this Regex may nest before it fails with
+    /// MatchError::RecursionLimitExceeded, which is DEFAULT_RECURSION_LIMIT otherwise.
+    /// Only the limit of the regex matching starts with counts, so it should be set last.
+    /// Every level takes some stack, so high limits need threads with large stacks.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::outcome::MatchError;
+    ///
+    /// let regex = Regex::parse("(a|b)*").unwrap().with_recursion_limit(100);
+    ///
+    /// assert!(regex.matches("abba"));
+    /// assert_eq!(Err(MatchError::RecursionLimitExceeded(100)), regex.try_matches(&"ab".repeat(100)));
+    /// ```
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
 
-        if let Err(StringPointerError::SizeExceeded) = match_result {
-            return false;
-        };
+    fn sequence_of(regexes: Vec<Regex<'a, T>>) -> Self {
+        Self::new_regex(Sequence::new(regexes))
+    }
+
+    /// Splits this chain into its single regexes, which have no following regex anymore.
+    fn into_elements(self) -> Vec<Regex<'a, T>> {
+        let mut elements = vec![];
+        let mut current = Some(self);
 
-        match_result.unwrap()
+        while let Some(mut regex) = current {
+            current = regex.next.take().map(|next_regex| *next_regex);
+            elements.push(regex);
+        }
+        elements
     }
 
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
-        match &self.repeat {
-            None => self.matches_string_without_repeat(string_pointer),
-            Some(repeat) => self.matches_string_with_repeat(string_pointer, repeat.get_minimum().unwrap_or(0), repeat.get_maximum())
+    /// Chains the given regexes together, in the given order.
+    fn from_elements(elements: Vec<Regex<'a, T>>) -> Option<Self> {
+        elements.into_iter().rev().fold(None, |chain, mut element| {
+            element.next = chain.map(Box::new);
+            Some(element)
+        })
+    }
+
+    /// Returns if this Regex matches the whole given slice of tokens.
+    /// Errors while matching count as no match, use try_matches_tokens to tell them apart.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+    ///
+    /// #[derive(PartialEq)]
+    /// enum Event { Open, Write(usize), Close }
+    ///
+    /// let write = Regex::token(|event| matches!(event, Event::Write(_)));
+    /// let session = Regex::token(|event| *event == Event::Open)
+    ///     + write.that_repeats(ZeroToInfinity)
+    ///     + Regex::token(|event| *event == Event::Close);
+    ///
+    /// assert!(session.matches_tokens(&[Event::Open, Event::Write(3), Event::Write(5), Event::Close]));
+    /// assert!(!session.matches_tokens(&[Event::Open, Event::Close, Event::Write(3)]));
+    /// ```
+    pub fn matches_tokens(&self, tokens: &[T]) -> bool where T: PartialEq {
+        match self.try_matches_tokens(tokens) {
+            Ok(outcome) => outcome.is_match(),
+            Err(_) => false
         }
     }
 
-    fn matches_string_without_repeat(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
-        let regex_matches_result = self.own_regex_matches(string_pointer);
-        if let Ok(true) = regex_matches_result {
-            return self.next_regex_matches(string_pointer);
+    /// Returns if this Regex matches the whole given slice of tokens, or the error that
+    /// prevented the decision.
+    pub fn try_matches_tokens(&self, tokens: &[T]) -> Result<MatchOutcome, MatchError> where T: PartialEq {
+        let mut state = MatchState::for_tokens(tokens, self.recursion_limit).with_groups_of(self);
+        self.matches_whole_string(&mut state).map(MatchOutcome::from)
+    }
+
+    /// Searches the given slice of tokens for the first part this Regex matches and returns
+    /// its start and end index. Of all matches starting at the leftmost position, the one
+    /// found first is taken.
+    pub fn find_tokens(&self, tokens: &[T]) -> Result<Option<(usize, usize)>, MatchError> where T: PartialEq {
+        let mut state = MatchState::for_tokens(tokens, self.recursion_limit).with_groups_of(self);
+        for start in 0..=tokens.len() {
+            if let Some(end) = self.matches_prefix_at(&mut state, start)? {
+                return Ok(Some((start, end)));
+            }
         }
-        regex_matches_result
+        Ok(None)
     }
 
-    fn matches_string_with_repeat(&self, string_pointer: &mut StringPointer, min_repeat: usize, max_repeat: Option<usize>) -> Result<bool, StringPointerError> {
-        match self.minimum_repeat_matches(string_pointer, min_repeat) {
-            Ok(true) => return Ok(true),
-            Err(error) => return Err(error),
-            _ => ()
+    /// Returns if this Regex matches the whole given string, or the error that prevented
+    /// the decision. Unlike try_matches, it is available for regexes over any tokens,
+    /// where only text matchers can match.
+    pub(crate) fn try_matches_text(&self, string: &str) -> Result<bool, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        self.matches_whole_string(&mut state)
+    }
+
+    /// Matches this Regex beginning at the given position of the MatchState and returns
+    /// where the first match found ends, or None if there is none. Used to match tokens.
+    /// The groups of this Regex must already be set on the MatchState, this only forgets
+    /// what an earlier attempt captured.
+    pub(crate) fn matches_prefix_at(&self, state: &mut MatchState<T>, position: usize) -> Result<Option<usize>, MatchError> {
+        state.clear_captures();
+        state.cursor.set_index(position);
+
+        let mut end = None;
+        self.matches_string(state, &mut |state| {
+            end = Some(state.cursor.index());
+            Ok(true)
+        })?;
+        Ok(end)
+    }
+
+    /// Returns if this Regex matches the whole string of the given MatchState.
+    fn matches_whole_string(&self, state: &mut MatchState<T>) -> Result<bool, MatchError> {
+        self.matches_string(state, &mut |state| {
+            let at_the_end = state.cursor.at_the_end();
+            if !at_the_end {
+                state.expect(state.cursor.index(), || String::from("the end of the string"));
+            }
+            Ok(at_the_end)
+        })
+    }
+
+    /// Returns if this Regex and its following regexes match the given MatchState,
+    /// followed by the given continuation.
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        state.enter_node(self)?;
+        let result = match &self.repeat {
+            None => self.matches_string_without_repeat(state, next),
+            Some(repeat) => self.matches_string_with_repeat(state, repeat.get_minimum().unwrap_or(0), repeat.get_maximum(), next)
+        };
+        state.exit_node(result == Ok(true));
+        result
+    }
+
+    fn matches_string_without_repeat(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        self.own_regex_matches(state, &mut |state| self.next_regex_matches(state, next))
+    }
+
+    /// Matches this Regex as often as possible, but at least the minimum amount of times
+    /// defined by the set Repeat. If the following regexes do not match afterwards,
+    /// the repeats are given back one by one.
+    fn matches_string_with_repeat(&self, state: &mut MatchState<T>, min_repeat: usize, max_repeat: Option<usize>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        match self.regex_type.single_end() && state.edit_costs().is_none() {
+            true => self.repeat_in_loop(state, min_repeat, max_repeat, next),
+            false => self.repeat_recursively(state, min_repeat, max_repeat, next)
         }
+    }
+
+    /// Repeats this Regex going one level deeper for every repeat, remembering where the
+    /// rest failed if the repeat has no maximum.
+    fn repeat_recursively(&self, state: &mut MatchState<T>, min_repeat: usize, max_repeat: Option<usize>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let mut failures = RepeatFailures::new(max_repeat.is_none() && state.edit_costs().is_none());
+        self.repeat_from(state, 0, min_repeat, max_repeat, &mut failures, next)
+    }
 
-        let mut counter = min_repeat;
-        while !self.counter_at_max(counter, &max_repeat) {
-            let own_regex_matches = self.own_regex_matches(string_pointer);
-            string_pointer.set_checkpoint();
+    /// Repeats this Regex, whose type ends at one position at most, without going one level
+    /// deeper for every repeat. Where each repeat ended is remembered instead, together with
+    /// the spans captured so far if the type has children, so they can be given back.
+    fn repeat_in_loop(&self, state: &mut MatchState<T>, min_repeat: usize, max_repeat: Option<usize>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let captures_inside = !self.regex_type.children().is_empty();
+        let mut ends = vec![state.cursor.index()];
+        let mut captures = vec![];
+        if captures_inside {
+            captures.push(state.capture_spans().to_vec());
+        }
 
-            match own_regex_matches {
-                Ok(true) => match self.next_regex_matches(string_pointer) {
-                    Ok(true) => return Ok(true),
-                    Ok(false) => string_pointer.return_to_checkpoint().unwrap(),
-                    Err(error) => return Err(error),
-                },
-                Ok(false) => {
-                    string_pointer.return_to_checkpoint().unwrap();
+        while !self.counter_at_max(ends.len() - 1, &max_repeat) {
+            state.step()?;
+            let counter = ends.len() - 1;
+            let start = state.checkpoint();
+            let mut end = start;
+            let repeat_matches = self.own_regex_matches(state, &mut |state| {
+                // A repeat that consumed nothing would loop forever
+                if counter >= min_repeat && state.cursor.index() == start {
                     return Ok(false);
                 }
-                Err(error) => return Err(error),
+                end = state.cursor.index();
+                Ok(true)
+            })?;
+
+            if !repeat_matches {
+                state.backtrack(start);
+                break;
+            }
+            ends.push(end);
+            if captures_inside {
+                captures.push(state.capture_spans().to_vec());
             }
         }
 
+        for counter in (0..ends.len()).rev() {
+            if counter >= min_repeat {
+                if captures_inside {
+                    state.restore_captures(&captures[counter]);
+                }
+                if self.next_regex_matches(state, next)? {
+                    return Ok(true);
+                }
+            }
+            if counter > 0 {
+                state.backtrack(ends[counter - 1]);
+            }
+        }
+
+        if captures_inside {
+            state.restore_captures(&captures[0]);
+        }
         Ok(false)
     }
 
-    /// Returns if this Regex matches the given StringPointer a minimum amount of times, defined
-    /// by the set Repeat. Afterwards, the following regexes must match the remaining
-    /// string. If this is not true, the StringPointer is reset to its index that was reached after
-    /// the repeats.
-    fn minimum_repeat_matches(&self, string_pointer: &mut StringPointer, min: usize) -> Result<bool, StringPointerError> {
-        for _ in 0..min {
-            match self.own_regex_matches(string_pointer) {
-                Ok(false) => return Ok(false),
-                Err(error) => return Err(error),
-                _ => ()
-            }
+    /// Matches this Regex from the given repeat on, going one level deeper for every repeat,
+    /// so that the ends of a repeat can be given back one by one as well.
+    ///
+    /// Past the minimum, where the rest failed before is remembered in the given failures,
+    /// so it is not tried again.
+    fn repeat_from(&self, state: &mut MatchState<T>, counter: usize, min_repeat: usize, max_repeat: Option<usize>, failures: &mut RepeatFailures, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        if counter >= min_repeat && failures.failed_before(state) {
+            return Ok(false);
         }
 
-        string_pointer.set_checkpoint();
+        if !self.counter_at_max(counter, &max_repeat) {
+            let start = state.checkpoint();
+            let repeat_matches = self.own_regex_matches(state, &mut |state| {
+                // A repeat that consumed nothing would loop forever
+                if counter >= min_repeat && state.cursor.index() == start {
+                    return Ok(false);
+                }
+                state.enter()?;
+                let result = self.repeat_from(state, counter + 1, min_repeat, max_repeat, failures, next);
+                state.exit();
+                result
+            })?;
 
-        match self.next_regex_matches(string_pointer) {
-            Ok(true) => Ok(true),
-            Err(error) => Err(error),
-            _ => {
-                string_pointer.return_to_checkpoint().unwrap();
-                Ok(false)
+            if repeat_matches {
+                return Ok(true);
             }
+            state.backtrack(start);
+        }
+
+        match counter >= min_repeat {
+            true => self.repeat_done(state, failures, next),
+            false => Ok(false)
+        }
+    }
+
+    /// Matches the following regexes after the last repeat of this Regex and remembers
+    /// in the given failures if they failed.
+    fn repeat_done(&self, state: &mut MatchState<T>, failures: &mut RepeatFailures, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let position = state.cursor.index();
+        let matches = self.next_regex_matches(state, next)?;
+        if !matches {
+            failures.remember(position, state.capture_spans());
         }
+        Ok(matches)
     }
 
     fn counter_at_max(&self, counter: usize, max_repeat: &Option<usize>) -> bool {
@@ -178,22 +593,313 @@ impl<'a> Regex<'a> {
         }
     }
 
-    /// Returns if this Regex, defined by its type, matches the given StringPointer.
-    fn own_regex_matches(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
-        self.regex_type.matches_string(string_pointer)
+    /// Returns if this Regex and its following regexes hand one end position at most
+    /// to their continuation, wherever they start.
+    fn has_single_end(&self) -> bool {
+        let mut regex = Some(self);
+        while let Some(current) = regex {
+            let fixed_repeat = current.repeat.as_ref()
+                .is_none_or(|repeat| repeat.get_maximum() == Some(repeat.get_minimum().unwrap_or(0)));
+            if !fixed_repeat || !current.regex_type.single_end() {
+                return false;
+            }
+            regex = current.next.as_deref();
+        }
+        true
+    }
+
+    /// Returns if this Regex, defined by its type, matches the given MatchState.
+    fn own_regex_matches(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        self.regex_type.matches_string(state, next)
+    }
+
+    /// Returns this Regex and all regexes inside it, in pre-order.
+    fn nodes(&self) -> Vec<&Regex<'a, T>> {
+        let mut nodes = vec![self];
+        for child in self.regex_type.children() {
+            nodes.extend(child.nodes());
+        }
+        if let Some(next_regex) = &self.next {
+            nodes.extend(next_regex.nodes());
+        }
+        nodes
+    }
+
+    /// Returns all groups inside this Regex, ordered by their number.
+    pub(crate) fn groups(&self) -> Vec<&Group<'a, T>> {
+        self.nodes().into_iter()
+            .filter_map(|node| node.regex_type.group())
+            .collect()
+    }
+
+    /// Returns if this Regex and its following regexes can match without consuming a char.
+    pub(crate) fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        let own_nullable = self.minimum_repeat() == 0 || self.regex_type.nullable(nullable_rules);
+        own_nullable && self.next.as_ref().is_none_or(|next_regex| next_regex.nullable(nullable_rules))
+    }
+
+    /// Returns the names of all rules this Regex and its following regexes
+    /// might call before consuming a char.
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        let mut leading_rules = self.regex_type.leading_rules(nullable_rules);
+        let own_nullable = self.minimum_repeat() == 0 || self.regex_type.nullable(nullable_rules);
+
+        if let (true, Some(next_regex)) = (own_nullable, &self.next) {
+            leading_rules.extend(next_regex.leading_rules(nullable_rules));
+        }
+        leading_rules
+    }
+
+    /// Returns how often this Regex must match at least.
+    fn minimum_repeat(&self) -> usize {
+        match &self.repeat {
+            Some(repeat) => repeat.get_minimum().unwrap_or(0),
+            None => 1
+        }
+    }
+
+    /// Describes this Regex alone, without the regexes inside or after it.
+    fn label(&self) -> String {
+        match &self.repeat {
+            Some(repeat) => format!("{} ({})", self.regex_type, repeat),
+            None => self.regex_type.to_string()
+        }
     }
 
-    /// Returns if the following Regex of this one matches the given StringPointer.
-    /// If no following Regex is set, the StringPointer must have reached its end.
-    fn next_regex_matches(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
+    /// Returns if the following Regex of this one matches the given MatchState.
+    /// If no following Regex is set, the continuation decides.
+    fn next_regex_matches(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
         match &self.next {
-            Some(next_regex) => next_regex.matches_string(string_pointer),
-            None => Ok(string_pointer.at_the_end())
+            Some(next_regex) => next_regex.matches_string(state, next),
+            None => next(state)
         }
     }
 }
 
-impl<'a> Display for Regex<'a> {
+impl<'a> Regex<'a> {
+    /// Creates a regex that matches like the rule with the given name.
+    /// Rule references can only be matched through a RuleSet.
+    pub fn rule(name: &str) -> Self {
+        Self::new_regex(RuleReference::new(name))
+    }
+
+    /// Returns if this Regex matches the whole given string.
+    /// Errors while matching count as no match, use try_matches to tell them apart.
+    pub fn matches(&self, string: &str) -> bool {
+        match self.try_matches(string) {
+            Ok(outcome) => outcome.is_match(),
+            Err(_) => false
+        }
+    }
+
+    /// Returns if this Regex matches the whole given string, or the error that
+    /// prevented the decision.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::outcome::{MatchError, MatchOutcome};
+    ///
+    /// let regex = Regex::parse("(foo|bar)*").unwrap();
+    ///
+    /// assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches("foobar"));
+    /// assert_eq!(Err(MatchError::RecursionLimitExceeded(2_000)), regex.try_matches(&"foo".repeat(5_000)));
+    /// ```
+    pub fn try_matches(&self, string: &str) -> Result<MatchOutcome, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        self.matches_whole_string(&mut state).map(MatchOutcome::from)
+    }
+
+    /// Like try_matches, but stops with MatchError::LimitExceeded after the given number of steps,
+    /// once the given deadline has passed or once the given flag is set, whichever comes first.
+    /// Every regex matching enters counts as a step. The deadline and the flag are only checked
+    /// every few hundred steps.
+    ///
+    /// Examples:
+    /// ```
+    /// use std::sync::atomic::AtomicBool;
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::outcome::{Limit, MatchError, MatchOutcome};
+    ///
+    /// let regex = Regex::parse("a*b").unwrap();
+    /// let cancelled = AtomicBool::new(false);
+    ///
+    /// assert_eq!(Ok(MatchOutcome::Matched), regex.matches_with_limits("aab", 100, None, &cancelled));
+    /// assert_eq!(Err(MatchError::LimitExceeded(Limit::Steps(100))), regex.matches_with_limits(&"a".repeat(500), 100, None, &cancelled));
+    /// ```
+    pub fn matches_with_limits(&self, string: &str, max_steps: usize, deadline: Option<Instant>, cancelled: &AtomicBool) -> Result<MatchOutcome, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit)
+            .with_groups_of(self)
+            .with_limits(max_steps, deadline, cancelled);
+        self.matches_whole_string(&mut state).map(MatchOutcome::from)
+    }
+
+    /// Returns if the given string matches this Regex, or if it could still match once more
+    /// chars are appended to it. Errors while matching count as Impossible, use
+    /// try_matches_prefix to tell them apart.
+    ///
+    /// Not, And and lookarounds are only approximated, so for regexes containing them
+    /// Incomplete might be returned although no appended chars would lead to a match.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::outcome::PrefixMatch;
+    ///
+    /// let date = Regex::parse(r"\d{4}-\d{2}-\d{2}").unwrap();
+    ///
+    /// assert_eq!(PrefixMatch::Complete, date.matches_prefix("2024-05-17"));
+    /// assert_eq!(PrefixMatch::Incomplete, date.matches_prefix("2024-0"));
+    /// assert_eq!(PrefixMatch::Impossible, date.matches_prefix("2024/"));
+    /// ```
+    pub fn matches_prefix(&self, string: &str) -> PrefixMatch {
+        self.try_matches_prefix(string).unwrap_or(PrefixMatch::Impossible)
+    }
+
+    /// Like matches_prefix, but returns the error that prevented the decision, if there is one.
+    pub fn try_matches_prefix(&self, string: &str) -> Result<PrefixMatch, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        if self.matches_whole_string(&mut state)? {
+            return Ok(PrefixMatch::Complete);
+        }
+
+        Ok(match state.more_input_expected() {
+            true => PrefixMatch::Incomplete,
+            false => PrefixMatch::Impossible
+        })
+    }
+
+    /// Like try_matches, but the given observer gets notified about every step of the attempt.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::regex::observer::TraceRecorder;
+    ///
+    /// let regex = Regex::from("foo") | "bar";
+    /// let mut recorder = TraceRecorder::new();
+    ///
+    /// assert!(regex.try_matches_observed("bar", &mut recorder).unwrap().is_match());
+    /// assert!(recorder.dump().contains("backtrack #0 from 3 to 0"));
+    /// ```
+    pub fn try_matches_observed(&self, string: &str, observer: &mut dyn MatchObserver) -> Result<MatchOutcome, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self).observed_by(observer, self);
+        self.matches_whole_string(&mut state).map(MatchOutcome::from)
+    }
+
+    /// Searches the given string for the first part this Regex matches and returns its
+    /// start and end char index. Of all matches starting at the leftmost position, the one
+    /// found first is taken. Positions where no match can start, because a literal every
+    /// match requires is missing, are skipped without running the matcher.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::any::Any;
+    ///
+    /// let regex = Regex::from("foo") + Regex::matcher(Any);
+    ///
+    /// assert_eq!(Some((4, 8)), regex.find("bar foo! baz").unwrap());
+    /// assert_eq!(None, regex.find("bar baz").unwrap());
+    /// ```
+    pub fn find(&self, string: &str) -> Result<Option<(usize, usize)>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        for start in self.candidate_starts(string) {
+            if let Some(end) = self.matches_prefix_at(&mut state, start)? {
+                return Ok(Some((start, end)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Searches the given string for all parts this Regex matches, from left to right,
+    /// and returns their start and end char indexes. The parts do not overlap,
+    /// after an empty match the search goes on one char later.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::parse(r"\d+").unwrap();
+    ///
+    /// assert_eq!(vec![(0, 2), (3, 4)], regex.find_all("12 3").unwrap());
+    /// ```
+    pub fn find_all(&self, string: &str) -> Result<Vec<(usize, usize)>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        let mut matches = vec![];
+        let mut position = 0;
+        for start in self.candidate_starts(string) {
+            if start < position {
+                continue;
+            }
+            if let Some(end) = self.matches_prefix_at(&mut state, start)? {
+                matches.push((start, end));
+                position = end.max(start + 1);
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Returns all char indexes of the given string where a match could start,
+    /// according to the literals of this Regex.
+    fn candidate_starts(&self, string: &str) -> Vec<usize> {
+        let literals = self.literals();
+        if !literals.prefix.is_empty() {
+            return literal_positions(string, &literals.prefix);
+        }
+
+        match literals.longest_required() {
+            Some(required) if !string.contains(required) => vec![],
+            _ => (0..=string.chars().count()).collect()
+        }
+    }
+
+    /// Returns the parts of the given string captured by the groups of this Regex,
+    /// or None if it does not match.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    /// use crate::readex::matcher::any::Any;
+    /// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+    ///
+    /// let name = Regex::matcher(Any).that_repeats(ZeroToInfinity);
+    /// let regex = Regex::from("<") + Regex::named_group("tag", name) + ">";
+    /// let captures = regex.captures("<b>").unwrap().unwrap();
+    ///
+    /// assert_eq!(Some(String::from("b")), captures.name("tag"));
+    /// assert_eq!(Some(String::from("<b>")), captures.get(0));
+    /// ```
+    pub fn captures(&self, string: &str) -> Result<Option<Captures>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        match self.matches_whole_string(&mut state)? {
+            true => Ok(Some(state.captures(string))),
+            false => Ok(None)
+        }
+    }
+
+    /// Explains why the given string does not match this Regex. Returns None if it matches.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::from("foo ") + (Regex::from("bar") | "baz");
+    /// let diagnosis = regex.diagnose("foo x").unwrap().unwrap();
+    ///
+    /// assert_eq!("at column 5: expected `bar` or `baz`, found `x`", diagnosis.to_string());
+    /// assert_eq!(None, regex.diagnose("foo bar").unwrap());
+    /// ```
+    pub fn diagnose(&self, string: &str) -> Result<Option<Diagnosis>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        match self.matches_whole_string(&mut state)? {
+            true => Ok(None),
+            false => Ok(Some(state.diagnosis()))
+        }
+    }
+}
+
+impl<'a, T> Display for Regex<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut regex_string = String::new();
         regex_string.push_str(format!("Type: {} \n", self.regex_type).as_str());
@@ -208,4 +914,366 @@ impl<'a> Display for Regex<'a> {
 
         write!(f, "{}", regex_string)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use crate::matcher::any::Any;
+    use crate::matcher::string::Str;
+    use crate::regex::{DEFAULT_MEMO_BUDGET, DEFAULT_RECURSION_LIMIT, Regex, RepeatFailures};
+    use crate::regex::observer::{MatchObserver, TraceEvent, TraceRecorder};
+    use crate::regex::outcome::{Limit, MatchError, MatchOutcome, PrefixMatch};
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_try_matches() {
+        let regex = Regex::matcher(Str::new("foo"))
+            .followed_by(Regex::matcher(Any).that_repeats(ZeroToInfinity));
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches("foobar"));
+        assert_eq!(Ok(MatchOutcome::NotMatched), regex.try_matches("fobar"));
+        assert_eq!(Ok(MatchOutcome::NotMatched), regex.try_matches("fo"))
+    }
+
+    #[test]
+    fn success_memoized_nested_repeats() {
+        let regex = Regex::sequence_of(vec![Regex::from("a").that_repeats(ZeroToInfinity)]).that_repeats(ZeroToInfinity) + "b";
+        let string = "a".repeat(200);
+
+        assert!(!regex.matches(&string));
+        assert_eq!(None, regex.find(&string).unwrap());
+        assert!(regex.matches(&format!("{}b", string)))
+    }
+
+    #[test]
+    fn success_memoized_nested_repeats_with_captures() {
+        // What the group captured is remembered as well, which takes more attempts
+        let regex = Regex::group(Regex::from("a").that_repeats(ZeroToInfinity)).that_repeats(ZeroToInfinity) + "b";
+        let string = "a".repeat(60);
+
+        assert!(!regex.matches(&string));
+        assert_eq!(None, regex.find(&string).unwrap());
+        assert!(regex.matches(&format!("{}b", string)))
+    }
+
+    #[test]
+    fn success_repeat_failures_within_budget() {
+        let mut failures = RepeatFailures::new(true);
+        for position in 0..DEFAULT_MEMO_BUDGET {
+            failures.remember(position, &[]);
+        }
+
+        assert!(failures.bytes <= DEFAULT_MEMO_BUDGET);
+        assert!(failures.failures.len() < DEFAULT_MEMO_BUDGET / 32)
+    }
+
+    #[test]
+    fn success_memoized_repeat_respects_captures() {
+        let regex = Regex::group(Regex::from("aa") | "a").that_repeats(ZeroToInfinity) + Regex::backreference(1);
+
+        assert!(regex.matches("aaa"));
+        assert!(regex.matches("aaaa"));
+        assert!(!regex.matches("aaab"))
+    }
+
+    #[test]
+    fn success_matches_with_limits() {
+        let regex = Regex::group(Regex::matcher(Any).that_repeats(ZeroToInfinity)) + Regex::backreference(1);
+        let cancelled = AtomicBool::new(false);
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.matches_with_limits("abab", 1_000, None, &cancelled));
+        assert_eq!(Ok(MatchOutcome::NotMatched), regex.matches_with_limits("abc", 1_000, Some(Instant::now() + Duration::from_secs(60)), &cancelled))
+    }
+
+    #[test]
+    fn failure_matches_with_limits_steps() {
+        let regex = Regex::group(Regex::matcher(Any).that_repeats(ZeroToInfinity)) + Regex::backreference(1);
+        let cancelled = AtomicBool::new(false);
+
+        assert_eq!(Err(MatchError::LimitExceeded(Limit::Steps(100))), regex.matches_with_limits(&"ab".repeat(300), 100, None, &cancelled))
+    }
+
+    #[test]
+    fn failure_matches_with_limits_deadline_and_cancelled() {
+        let regex = Regex::matcher(Any).that_repeats(ZeroToInfinity);
+        let cancelled = AtomicBool::new(false);
+
+        assert_eq!(Err(MatchError::LimitExceeded(Limit::Deadline)), regex.matches_with_limits("abc", 1_000, Some(Instant::now()), &cancelled));
+        cancelled.store(true, Ordering::Relaxed);
+        assert_eq!(Err(MatchError::LimitExceeded(Limit::Cancelled)), regex.matches_with_limits("abc", 1_000, None, &cancelled))
+    }
+
+    #[test]
+    fn success_try_matches_close_to_recursion_limit() {
+        // Every repeat of an alternation takes two levels
+        let regex = (Regex::from("a") | "b").that_repeats(ZeroToInfinity);
+        let string = "a".repeat(DEFAULT_RECURSION_LIMIT / 2 - 1);
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches(&string))
+    }
+
+    #[test]
+    fn success_repeats_of_long_strings() {
+        let string = "a".repeat(3 * DEFAULT_RECURSION_LIMIT);
+
+        assert!(Regex::parse(".*").unwrap().matches(&string));
+        assert!(Regex::parse("a{6000}").unwrap().matches(&string));
+        assert_eq!(Ok(vec![(0, 2500)]), Regex::parse(r"\d+").unwrap().find_all(&"1".repeat(2500)));
+        assert_eq!(Ok(Some((0, 2102))), Regex::parse("[a-z]+@[a-z]+").unwrap().find(&format!("{}@b", "a".repeat(2100))))
+    }
+
+    #[test]
+    fn success_repeat_of_chain_repeats_head() {
+        let regex = Regex::from("a").followed_by(Regex::from("b")).that_repeats(ZeroToInfinity);
+
+        assert!(regex.matches("aab"));
+        assert!(regex.matches("b"));
+        assert!(!regex.matches("abab"))
+    }
+
+    #[test]
+    fn success_repeats_give_back_captures() {
+        let captures = Regex::parse(r"(\d)*\d").unwrap().captures("123").unwrap().unwrap();
+
+        assert_eq!(Some(String::from("2")), captures.get(1))
+    }
+
+    #[test]
+    fn success_diagnose() {
+        let regex = Regex::matcher(Str::new("foo"))
+            .followed_by(Regex::matcher(Str::new("A")).that_repeats(ZeroToInfinity))
+            .followed_by(Regex::matcher(Str::new("bar")));
+
+        let diagnosis = regex.diagnose("fooAAbax").unwrap().unwrap();
+        assert_eq!(5, diagnosis.position);
+        assert_eq!(vec![String::from("`A`"), String::from("`bar`")], diagnosis.expected);
+        assert_eq!(Some('b'), diagnosis.found)
+    }
+
+    #[test]
+    fn success_diagnose_expects_end() {
+        let regex = Regex::matcher(Str::new("foo"));
+
+        let diagnosis = regex.diagnose("foobar").unwrap().unwrap();
+        assert_eq!("at column 4: expected the end of the string, found `b`", diagnosis.to_string())
+    }
+
+    #[test]
+    fn success_diagnose_ignores_not() {
+        let regex = Regex::not(Regex::matcher(Str::new("foo")))
+            .followed_by(Regex::matcher(Str::new("bar")));
+
+        let diagnosis = regex.diagnose("foobar").unwrap().unwrap();
+        assert_eq!(vec![String::from("`bar`")], diagnosis.expected)
+    }
+
+    #[test]
+    fn success_matches_prefix() {
+        let regex = Regex::from("foo") + Regex::matcher(Str::new("ba")).that_repeats(ZeroToInfinity) + "r";
+
+        assert_eq!(PrefixMatch::Incomplete, regex.matches_prefix(""));
+        assert_eq!(PrefixMatch::Incomplete, regex.matches_prefix("fooba"));
+        assert_eq!(PrefixMatch::Incomplete, regex.matches_prefix("foob"));
+        assert_eq!(PrefixMatch::Complete, regex.matches_prefix("foobar"));
+        assert_eq!(PrefixMatch::Impossible, regex.matches_prefix("foobx"));
+        assert_eq!(PrefixMatch::Impossible, regex.matches_prefix("foorr"))
+    }
+
+    #[test]
+    fn success_matches_prefix_with_backreference() {
+        let regex = Regex::group(Regex::from("ab") | "c") + "-" + Regex::backreference(1);
+
+        assert_eq!(PrefixMatch::Incomplete, regex.matches_prefix("ab-a"));
+        assert_eq!(PrefixMatch::Impossible, regex.matches_prefix("ab-b"));
+        assert_eq!(PrefixMatch::Complete, regex.matches_prefix("c-c"))
+    }
+
+    #[test]
+    fn success_matches_prefix_ignores_lookbehind() {
+        let regex = Regex::from("a") + Regex::lookbehind(Regex::from("ab"));
+
+        assert_eq!(PrefixMatch::Impossible, regex.matches_prefix("a"))
+    }
+
+    #[test]
+    fn success_try_matches_observed() {
+        let regex = Regex::matcher(Str::new("A")).that_repeats(ZeroToInfinity)
+            .followed_by(Regex::matcher(Str::new("b")));
+        let mut recorder = TraceRecorder::new();
+
+        regex.try_matches_observed("Ab", &mut recorder).unwrap();
+
+        assert_eq!(&[
+            TraceEvent::Enter { node: 0, position: 0 },
+            TraceEvent::Checkpoint { node: 0, position: 0 },
+            TraceEvent::Checkpoint { node: 0, position: 1 },
+            TraceEvent::Backtrack { node: 0, from: 2, to: 1 },
+            TraceEvent::Enter { node: 1, position: 1 },
+            TraceEvent::Exit { node: 1, position: 2, matched: true },
+            TraceEvent::Exit { node: 0, position: 2, matched: true },
+        ], recorder.events());
+        assert_eq!("\
+enter #0 Match `A` (ZtI) at 0
+  checkpoint #0 at 0
+  checkpoint #0 at 1
+  backtrack #0 from 2 to 1
+  enter #1 Match `b` at 1
+  exit #1 at 2: matched
+exit #0 at 2: matched
+", recorder.dump())
+    }
+
+    #[test]
+    fn success_dump_unbalanced_trace() {
+        let mut recorder = TraceRecorder::new();
+        recorder.exit(0, 1, false);
+        recorder.enter(0, 1);
+
+        assert_eq!("exit #0 at 1: failed\nenter #0  at 1\n", recorder.dump())
+    }
+
+    #[test]
+    fn success_backreference() {
+        let tag = Regex::matcher(Any).that_repeats(ZeroToInfinity);
+        let content = Regex::matcher(Any).that_repeats(ZeroToInfinity);
+        let regex = Regex::from("<") + Regex::named_group("tag", tag) + ">"
+            + content
+            + "</" + Regex::named_backreference("tag") + ">";
+
+        assert!(regex.matches("<b>bold</b>"));
+        assert!(regex.matches("<b>x</i> y</b>"));
+        assert!(!regex.matches("<b>bold</i>"))
+    }
+
+    #[test]
+    fn success_backreference_backtracks_captures() {
+        let regex = Regex::group(Regex::from("a") | "ab") + "b" + Regex::backreference(1);
+
+        assert!(regex.matches("aba"));
+        assert!(regex.matches("abbab"));
+        assert!(!regex.matches("abab"))
+    }
+
+    #[test]
+    fn success_captures() {
+        let regex = Regex::group(Regex::from("foo") | "bar") + Regex::group(Regex::matcher(Any).that_repeats(ZeroToInfinity));
+        let captures = regex.captures("barbaz").unwrap().unwrap();
+
+        assert_eq!(3, captures.len());
+        assert_eq!(Some(String::from("bar")), captures.get(1));
+        assert_eq!(Some(String::from("baz")), captures.get(2));
+        assert_eq!(Some((3, 6)), captures.span(2));
+        assert_eq!(None, regex.captures("bazbar").unwrap())
+    }
+
+    #[test]
+    fn success_find() {
+        let regex = Regex::from("fo") + Regex::matcher(Str::new("o")).that_repeats(ZeroToInfinity);
+
+        assert_eq!(Some((4, 9)), regex.find("bar foooo fo").unwrap());
+        assert_eq!(Some((0, 2)), regex.find("fo").unwrap());
+        assert_eq!(None, regex.find("f o").unwrap())
+    }
+
+    #[test]
+    fn success_find_without_prefix() {
+        let regex = Regex::matcher(Any) + "ar";
+
+        assert_eq!(Some((4, 7)), regex.find("foo bar").unwrap());
+        assert_eq!(None, regex.find("foo baz").unwrap());
+        assert_eq!(Some((0, 0)), Regex::from("").find("foo").unwrap())
+    }
+
+    #[test]
+    fn success_find_all_forgets_captures() {
+        let regex = Regex::parse(r"(?:(a)|x)b\1").unwrap();
+
+        assert_eq!(Ok(vec![(0, 3)]), regex.find_all("abaxba"));
+        assert_eq!(Ok(None), regex.find("xba"))
+    }
+
+    #[test]
+    fn success_find_with_lookbehind() {
+        let regex = Regex::negative_lookbehind(Regex::from("-")) + "5";
+
+        assert_eq!(Some((4, 5)), regex.find("-5, 5").unwrap())
+    }
+
+    #[test]
+    fn success_sequence_of_many_regexes() {
+        let tokens: Vec<String> = (0..1_000).map(|index| format!("<{}>", index)).collect();
+        let regex = Regex::sequence(tokens.iter().map(|token| Regex::from(token.as_str())));
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches(&tokens.concat()));
+        assert_eq!(Ok(MatchOutcome::NotMatched), regex.try_matches(&tokens[1..].concat()))
+    }
+
+    #[test]
+    fn success_appending_to_short_sequences() {
+        let empty = Regex::sequence(vec![]);
+        assert!(empty.matches(""));
+        assert!(!empty.matches("a"));
+
+        let mut regex = Regex::sequence(vec![Regex::from("a")]);
+        for _ in 0..10_000 {
+            regex = regex.followed_by(Regex::from("b"));
+        }
+        assert_eq!(10_002, regex.nodes().len());
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches(&format!("a{}", "b".repeat(10_000))))
+    }
+
+    #[test]
+    fn success_sequence_deeper_than_recursion_limit() {
+        let tokens: Vec<String> = (0..10_000).map(|index| format!("<{}>", index)).collect();
+        let regex = Regex::sequence(tokens.iter().map(|token| Regex::group(Regex::from(token.as_str()))));
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches(&tokens.concat()));
+        assert_eq!(Ok(MatchOutcome::NotMatched), regex.try_matches(&tokens[1..].concat()));
+        assert_eq!(Ok(Some((1, tokens.concat().len() + 1))), regex.find(&format!("!{}", tokens.concat())))
+    }
+
+    #[test]
+    fn success_followed_by_appends_to_sequence() {
+        let start = Regex::sequence(vec![Regex::from("a"), Regex::from("b")]);
+        let regex = (0..1_000).fold(start, |regex, _| regex + Regex::matcher(Any).that_repeats(ZeroToInfinity));
+
+        assert_eq!(1_003, regex.nodes().len());
+        assert!(regex.matches("abc"))
+    }
+
+    #[test]
+    fn success_any_of_many_regexes() {
+        let regex = Regex::any_of((0..10_000).map(|index| Regex::from(index.to_string().as_str())));
+
+        assert!(regex.matches("9999"));
+        assert!(!regex.matches("10000"));
+        assert!(!Regex::any_of(vec![]).matches(""))
+    }
+
+    #[test]
+    fn failure_backreference_unknown_group() {
+        let regex = Regex::group(Regex::from("a")) + Regex::backreference(2);
+
+        assert_eq!(Err(MatchError::UnknownGroup(String::from("2"))), regex.try_matches("aa"));
+        assert_eq!(Err(MatchError::UnknownGroup(String::from("<foo>"))), Regex::named_backreference("foo").try_matches("a"))
+    }
+
+    #[test]
+    fn failure_try_matches_recursion_limit_exceeded() {
+        let regex = (Regex::from("a") | "b").that_repeats(ZeroToInfinity);
+        let string = "a".repeat(DEFAULT_RECURSION_LIMIT / 2);
+
+        assert_eq!(Err(MatchError::RecursionLimitExceeded(DEFAULT_RECURSION_LIMIT)), regex.try_matches(&string));
+        assert!(!regex.matches(&string))
+    }
+
+    #[test]
+    fn failure_try_matches_own_recursion_limit_exceeded() {
+        let regex = (Regex::from("a") | "b").that_repeats(ZeroToInfinity).with_recursion_limit(10);
+
+        assert_eq!(Ok(MatchOutcome::Matched), regex.try_matches("abab"));
+        assert_eq!(Err(MatchError::RecursionLimitExceeded(10)), regex.try_matches("ababa"))
+    }
+}
@@ -1,29 +1,106 @@
 use std::fmt::{Display, Formatter};
 
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
 use crate::regex::Regex;
-use crate::regex::regex_type::RegexType;
-use crate::string_pointer::{StringPointer, StringPointerError};
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
 
-pub struct Not<'a> {
-    inner: Regex<'a>
+/// Matches every part of the string the inner regex does not match.
+/// Longer parts are tried first.
+pub struct Not<'a, T = char> {
+    inner: Regex<'a, T>
 }
 
-impl<'a> RegexType for Not<'a> {
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
+impl<'a, T> RegexType<'a, T> for Not<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        // More input would give more ends to try
+        state.expect_more_input(|| None);
+
+        let start = state.checkpoint();
+        let captures = state.capture_spans().to_vec();
+        for end in (start..=state.cursor.len()).rev() {
+            state.backtrack(start);
+            let inner_matches = state.silenced(|state| state.exactly(|state| self.inner.matches_string(state, &mut |state| Ok(state.cursor.index() == end))))?;
+            // Groups of the inner regex are no part of the match
+            state.restore_captures(&captures);
+
+            if !inner_matches {
+                state.cursor.set_index(end);
+                if next(state)? {
+                    return Ok(true);
+                }
+            }
+        }
+
         Ok(false)
     }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![&self.inner]
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        Ok(nfa.embed(&self.inner.to_dfa_over(alphabet)?.complement()))
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        output.push_str(&generator.generate_near_miss_of(&self.inner)?);
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        Regex::not(self.inner.simplified(stats))
+    }
 }
 
-impl<'a> Not<'a> {
-    pub fn new(inner: Regex<'a>) -> Self {
+impl<'a, T> Not<'a, T> {
+    pub fn new(inner: Regex<'a, T>) -> Self {
         Not {
             inner
         }
     }
 }
 
-impl<'a> Display for Not<'a> {
+impl<'a, T> Display for Not<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Not")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::string::Str;
+    use crate::regex::Regex;
+
+    #[test]
+    fn success_matches() {
+        let regex = Regex::not(Regex::matcher(Str::new("foo")));
+
+        assert!(regex.matches("bar"));
+        assert!(regex.matches("fo"));
+        assert!(regex.matches(""));
+        assert!(!regex.matches("foo"))
+    }
+
+    #[test]
+    fn success_matches_followed_by() {
+        let regex = Regex::not(Regex::matcher(Str::new("foo")))
+            .followed_by(Regex::matcher(Str::new("bar")));
+
+        assert!(regex.matches("bazbar"));
+        assert!(!regex.matches("foobar"))
+    }
+
+    #[test]
+    fn failure_backreference_to_group_inside() {
+        let regex = Regex::not(Regex::group(Regex::from("a"))) + Regex::backreference(1);
+
+        assert!(!regex.matches("a"));
+        assert!(!regex.matches("ba"))
+    }
+}
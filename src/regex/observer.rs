@@ -0,0 +1,104 @@
+use std::fmt::Write;
+
+/// Identifies a Regex node inside the regex a match was started on. Nodes are numbered
+/// in pre-order, starting with 0 for the root: first a regex, then the regexes
+/// inside its type, then its following regex.
+pub type NodeId = usize;
+
+/// Gets notified about every step of a matching attempt.
+/// All methods do nothing by default, so only the interesting ones must be implemented.
+pub trait MatchObserver {
+    /// Called once before matching starts, with a label for every node, indexed by its NodeId.
+    fn nodes(&mut self, _labels: &[String]) {}
+
+    /// A node starts matching at the given position.
+    fn enter(&mut self, _node: NodeId, _position: usize) {}
+
+    /// A node finished matching, together with everything that follows it.
+    fn exit(&mut self, _node: NodeId, _position: usize, _matched: bool) {}
+
+    /// A node remembers the given position to return to it later.
+    fn checkpoint(&mut self, _node: NodeId, _position: usize) {}
+
+    /// A node returns from one position to an earlier one to try another way of matching.
+    fn backtrack(&mut self, _node: NodeId, _from: usize, _to: usize) {}
+}
+
+/// A single step of a matching attempt, as recorded by the TraceRecorder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+    Enter { node: NodeId, position: usize },
+    Exit { node: NodeId, position: usize, matched: bool },
+    Checkpoint { node: NodeId, position: usize },
+    Backtrack { node: NodeId, from: usize, to: usize },
+}
+
+/// A MatchObserver that records every step, to dump them for debugging a regex.
+#[derive(Default)]
+pub struct TraceRecorder {
+    labels: Vec<String>,
+    events: Vec<TraceEvent>,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        TraceRecorder::default()
+    }
+
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Returns the recorded steps, one per line, indented by the nesting of the nodes.
+    pub fn dump(&self) -> String {
+        let mut dump = String::new();
+        let mut depth: usize = 0;
+
+        for event in &self.events {
+            if let TraceEvent::Exit { .. } = event {
+                depth = depth.saturating_sub(1);
+            }
+            let indent = "  ".repeat(depth);
+
+            // Writing to a String never fails
+            let _ = match event {
+                TraceEvent::Enter { node, position } => writeln!(dump, "{}enter #{} {} at {}", indent, node, self.label(*node), position),
+                TraceEvent::Exit { node, position, matched } => writeln!(dump, "{}exit #{} at {}: {}", indent, node, position, if *matched { "matched" } else { "failed" }),
+                TraceEvent::Checkpoint { node, position } => writeln!(dump, "{}checkpoint #{} at {}", indent, node, position),
+                TraceEvent::Backtrack { node, from, to } => writeln!(dump, "{}backtrack #{} from {} to {}", indent, node, from, to),
+            };
+
+            if let TraceEvent::Enter { .. } = event {
+                depth += 1;
+            }
+        }
+
+        dump
+    }
+
+    fn label(&self, node: NodeId) -> &str {
+        self.labels.get(node).map(String::as_str).unwrap_or("")
+    }
+}
+
+impl MatchObserver for TraceRecorder {
+    fn nodes(&mut self, labels: &[String]) {
+        self.labels = labels.to_vec()
+    }
+
+    fn enter(&mut self, node: NodeId, position: usize) {
+        self.events.push(TraceEvent::Enter { node, position })
+    }
+
+    fn exit(&mut self, node: NodeId, position: usize, matched: bool) {
+        self.events.push(TraceEvent::Exit { node, position, matched })
+    }
+
+    fn checkpoint(&mut self, node: NodeId, position: usize) {
+        self.events.push(TraceEvent::Checkpoint { node, position })
+    }
+
+    fn backtrack(&mut self, node: NodeId, from: usize, to: usize) {
+        self.events.push(TraceEvent::Backtrack { node, from, to })
+    }
+}
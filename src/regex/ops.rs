@@ -0,0 +1,132 @@
+use std::iter::FromIterator;
+use std::ops::{Add, BitAnd, BitOr, Not};
+
+use crate::matcher::string::Str;
+use crate::regex::Regex;
+
+/// `left | right` is the same as `Regex::or(left, right)`.
+impl<'a, T, R: Into<Regex<'a, T>>> BitOr<R> for Regex<'a, T> {
+    type Output = Regex<'a, T>;
+
+    fn bitor(self, right: R) -> Self::Output {
+        Regex::or(self, right.into())
+    }
+}
+
+/// `left & right` is the same as `Regex::and(left, right)`.
+impl<'a, T, R: Into<Regex<'a, T>>> BitAnd<R> for Regex<'a, T> {
+    type Output = Regex<'a, T>;
+
+    fn bitand(self, right: R) -> Self::Output {
+        Regex::and(self, right.into())
+    }
+}
+
+/// `!inner` is the same as `Regex::not(inner)`.
+impl<'a, T> Not for Regex<'a, T> {
+    type Output = Regex<'a, T>;
+
+    fn not(self) -> Self::Output {
+        Regex::not(self)
+    }
+}
+
+/// `first + next` matches like `first.followed_by(next)`, but puts both into a sequence,
+/// so that chains of `+` take linear time to build.
+impl<'a, T, R: Into<Regex<'a, T>>> Add<R> for Regex<'a, T> {
+    type Output = Regex<'a, T>;
+
+    fn add(self, next: R) -> Self::Output {
+        match self.regex_type.is_sequence() && self.repeat.is_none() && self.next.is_none() {
+            true => self.followed_by(next.into()),
+            false => Regex::sequence_of(vec![self, next.into()])
+        }
+    }
+}
+
+/// Collecting regexes is the same as `Regex::sequence`.
+impl<'a, T> FromIterator<Regex<'a, T>> for Regex<'a, T> {
+    fn from_iter<I: IntoIterator<Item = Regex<'a, T>>>(regexes: I) -> Self {
+        Regex::sequence(regexes)
+    }
+}
+
+/// Creates a Regex that matches the given literal.
+impl<'a> From<&str> for Regex<'a> {
+    fn from(string: &str) -> Self {
+        Regex::matcher(Str::new(string))
+    }
+}
+
+/// Creates a Regex that matches the given char.
+impl<'a> From<char> for Regex<'a> {
+    fn from(c: char) -> Self {
+        Regex::matcher(Str::new(c.encode_utf8(&mut [0; 4])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::regex::Regex;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_add_builds_sequence() {
+        let regex = (0..10_000).fold(Regex::from("<"), |regex, _| regex + "a") + ">";
+
+        assert_eq!(10_003, regex.nodes().len());
+        assert!(regex.matches(&format!("<{}>", "a".repeat(10_000))));
+        assert!(!regex.matches(&format!("<{}>", "a".repeat(9_999))))
+    }
+
+    #[test]
+    fn success_from_iterator() {
+        let regex: Regex = "a1b2".chars().map(Regex::from).collect();
+
+        assert!(regex.matches("a1b2"));
+        assert!(!regex.matches("a1b"))
+    }
+
+    #[test]
+    fn success_or() {
+        let regex = Regex::from("foo") | "bar";
+
+        assert!(regex.matches("foo"));
+        assert!(regex.matches("bar"));
+        assert!(!regex.matches("baz"))
+    }
+
+    #[test]
+    fn success_and() {
+        let regex = Regex::matcher(Any).that_repeats(ZeroToInfinity) & "foo";
+
+        assert!(regex.matches("foo"));
+        assert!(!regex.matches("bar"))
+    }
+
+    #[test]
+    fn success_not() {
+        let regex = !Regex::from("foo");
+
+        assert!(regex.matches("bar"));
+        assert!(!regex.matches("foo"))
+    }
+
+    #[test]
+    fn success_add() {
+        let regex = Regex::from("foo") + 'ß' + "bar";
+
+        assert!(regex.matches("fooßbar"));
+        assert!(!regex.matches("foobar"))
+    }
+
+    #[test]
+    fn success_combined() {
+        let regex = (Regex::from("foo") | "bar") + !Regex::from('x') + "baz";
+
+        assert!(regex.matches("foo-baz"));
+        assert!(regex.matches("barbaz"));
+        assert!(!regex.matches("fooxbaz"))
+    }
+}
@@ -1,22 +1,68 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
 use crate::regex::Regex;
-use crate::regex::regex_type::RegexType;
-use crate::string_pointer::{StringPointer, StringPointerError};
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::{simplify_alternatives, SimplifyStats};
 
-pub struct Or<'a> {
-    left: Regex<'a>,
-    right: Regex<'a>,
+/// Matches if either the left or the right regex matches. The left one is tried first.
+pub struct Or<'a, T = char> {
+    left: Regex<'a, T>,
+    right: Regex<'a, T>,
 }
 
-impl<'a> RegexType for Or<'a> {
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError> {
-        Ok(false)
+impl<'a, T> RegexType<'a, T> for Or<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let start = state.checkpoint();
+        if self.left.matches_string(state, next)? {
+            return Ok(true);
+        }
+
+        state.backtrack(start);
+        self.right.matches_string(state, next)
+    }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        self.left.nullable(nullable_rules) || self.right.nullable(nullable_rules)
+    }
+
+    fn literals(&self) -> Literals {
+        self.left.literals().either(self.right.literals())
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        let left = self.left.to_nfa(nfa, alphabet)?;
+        let right = self.right.to_nfa(nfa, alphabet)?;
+        Ok(nfa.union(left, right))
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        match generator.random_below(2) {
+            0 => self.left.generate_into(generator, output),
+            _ => self.right.generate_into(generator, output)
+        }
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        let left = self.left.simplified(stats);
+        let right = self.right.simplified(stats);
+        simplify_alternatives(left, right, stats)
     }
 }
 
-impl<'a> Or<'a> {
-    pub fn new(left: Regex<'a>, right: Regex<'a>) -> Self {
+impl<'a, T> Or<'a, T> {
+    pub fn new(left: Regex<'a, T>, right: Regex<'a, T>) -> Self {
         Or {
             left,
             right,
@@ -24,8 +70,31 @@ impl<'a> Or<'a> {
     }
 }
 
-impl<'a> Display for Or<'a> {
+impl<'a, T> Display for Or<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Or")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::string::Str;
+    use crate::regex::Regex;
+
+    #[test]
+    fn success_matches() {
+        let regex = Regex::or(Regex::matcher(Str::new("foo")), Regex::matcher(Str::new("bar")));
+
+        assert!(regex.matches("foo"));
+        assert!(regex.matches("bar"));
+        assert!(!regex.matches("baz"))
+    }
+
+    #[test]
+    fn success_matches_with_backtracking() {
+        let regex = Regex::or(Regex::matcher(Str::new("foo")), Regex::matcher(Str::new("foob")))
+            .followed_by(Regex::matcher(Str::new("ar")));
+
+        assert!(regex.matches("foobar"))
+    }
+}
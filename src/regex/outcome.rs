@@ -0,0 +1,74 @@
+use std::fmt::Formatter;
+
+/// The result of a successful matching attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOutcome {
+    Matched,
+    NotMatched,
+}
+
+impl MatchOutcome {
+    pub fn is_match(&self) -> bool {
+        *self == MatchOutcome::Matched
+    }
+}
+
+impl From<bool> for MatchOutcome {
+    fn from(matched: bool) -> Self {
+        match matched {
+            true => MatchOutcome::Matched,
+            false => MatchOutcome::NotMatched
+        }
+    }
+}
+
+/// How a string relates to a Regex, when more chars might still be appended to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixMatch {
+    /// The string matches as it is.
+    Complete,
+    /// The string does not match yet, but appending chars could make it match.
+    Incomplete,
+    /// Neither the string nor anything starting with it matches.
+    Impossible,
+}
+
+/// A limit set for a matching attempt, which stopped it when it was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// Matching took more than the given number of steps.
+    Steps(usize),
+    /// Matching was still going on at its deadline.
+    Deadline,
+    /// Matching was cancelled from the outside.
+    Cancelled,
+}
+
+/// Everything that can stop a matching attempt before it could decide
+/// whether the string matches or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchError {
+    /// The regex nested deeper than the given limit while matching.
+    RecursionLimitExceeded(usize),
+    /// Matching ran into one of the limits set for it.
+    LimitExceeded(Limit),
+    /// A backreference refers to a group the regex does not contain.
+    UnknownGroup(String),
+    /// A rule reference refers to a rule that is not defined.
+    UnknownRule(String),
+}
+
+impl std::error::Error for MatchError {}
+
+impl std::fmt::Display for MatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RecursionLimitExceeded(limit) => write!(f, "Matching exceeded the recursion limit of {}!", limit),
+            Self::LimitExceeded(Limit::Steps(steps)) => write!(f, "Matching exceeded the limit of {} steps!", steps),
+            Self::LimitExceeded(Limit::Deadline) => write!(f, "Matching exceeded its deadline!"),
+            Self::LimitExceeded(Limit::Cancelled) => write!(f, "Matching was cancelled!"),
+            Self::UnknownGroup(group) => write!(f, "The regex contains no group {}!", group),
+            Self::UnknownRule(rule) => write!(f, "There is no rule named {}!", rule)
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use std::fmt::Formatter;
+
+use crate::matcher::any::Any;
+use crate::matcher::class::Class;
+use crate::regex::Regex;
+use crate::repeat::between::Between;
+
+impl<'a> Regex<'a> {
+    /// Builds a Regex from a pattern in the usual regex syntax:
+    ///
+    /// - `.` any char, `[a-z]` and `[^a-z]` classes, `\d`, `\w`, `\s` and their negations `\D`, `\W`, `\S`
+    /// - `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}` repeats, which are always greedy
+    /// - `a|b` alternatives, `(...)` groups, `(?<name>...)` named groups and `(?:...)` plain parentheses
+    /// - `\1` and `\k<name>` backreferences
+    /// - `(?=...)`, `(?!...)`, `(?<=...)` and `(?<!...)` lookarounds, `^` and `$` for the start and end
+    ///
+    /// Other chars match themselves, a backslash in front of a special char makes it match itself.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::parse(r"(?<key>\w+)=\d{1,3}").unwrap();
+    ///
+    /// assert!(regex.matches("port=80"));
+    /// assert!(!regex.matches("port=8080"));
+    /// assert_eq!("at column 3: nothing to repeat before `*`", Regex::parse("a**").err().unwrap().to_string());
+    /// ```
+    pub fn parse(pattern: &str) -> Result<Regex<'a>, PatternError> {
+        let mut parser = PatternParser { chars: pattern.chars().collect(), position: 0 };
+        let regex = parser.alternatives()?;
+        match parser.peek() {
+            Some(found) => Err(PatternError::UnexpectedChar { position: parser.position, found }),
+            None => Ok(regex)
+        }
+    }
+}
+
+/// A piece of a pattern: either a plain char, which gets merged with its neighbours, or a Regex.
+enum Atom<'a> {
+    Char(char),
+    Regex(Regex<'a>),
+}
+
+/// What an escape sequence stands for, when it is usable inside a class.
+enum ClassItem {
+    Char(char),
+    Ranges(Vec<(char, char)>),
+}
+
+struct PatternParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl PatternParser {
+    fn alternatives<'a>(&mut self) -> Result<Regex<'a>, PatternError> {
+        let mut regex = self.sequence()?;
+        while self.eat('|') {
+            regex = Regex::or(regex, self.sequence()?);
+        }
+        Ok(regex)
+    }
+
+    fn sequence<'a>(&mut self) -> Result<Regex<'a>, PatternError> {
+        let mut regexes: Vec<Regex<'a>> = vec![];
+        let mut literal = String::new();
+
+        while !matches!(self.peek(), None | Some('|') | Some(')')) {
+            let atom = self.atom()?;
+            let repeat = self.repeat()?;
+
+            match (atom, repeat) {
+                (Atom::Char(c), None) => literal.push(c),
+                (atom, repeat) => {
+                    if !literal.is_empty() {
+                        regexes.push(Regex::from(literal.as_str()));
+                        literal.clear();
+                    }
+                    let regex = match atom {
+                        Atom::Char(c) => Regex::from(c),
+                        Atom::Regex(regex) => regex
+                    };
+                    regexes.push(match repeat {
+                        Some(repeat) => regex.that_repeats(repeat),
+                        None => regex
+                    });
+                }
+            }
+        }
+        if !literal.is_empty() {
+            regexes.push(Regex::from(literal.as_str()));
+        }
+
+        Ok(Regex::sequence(regexes))
+    }
+
+    fn atom<'a>(&mut self) -> Result<Atom<'a>, PatternError> {
+        let position = self.position;
+        let c = self.next("a char")?;
+        Ok(match c {
+            '(' => Atom::Regex(self.group()?),
+            '[' => Atom::Regex(self.class()?),
+            '.' => Atom::Regex(Regex::matcher(Any)),
+            '^' => Atom::Regex(Regex::negative_lookbehind(Regex::matcher(Any))),
+            '$' => Atom::Regex(Regex::negative_lookahead(Regex::matcher(Any))),
+            '\\' => self.escape()?,
+            '*' | '+' | '?' | '{' => return Err(PatternError::NothingToRepeat { position, found: c }),
+            c => Atom::Char(c)
+        })
+    }
+
+    /// Parses the rest of a group, after its opening parenthesis.
+    fn group<'a>(&mut self) -> Result<Regex<'a>, PatternError> {
+        let regex = match self.eat('?') {
+            false => Regex::group(self.alternatives()?),
+            true => {
+                let position = self.position;
+                match self.next("`:`, `=`, `!` or `<`")? {
+                    ':' => self.alternatives()?,
+                    '=' => Regex::lookahead(self.alternatives()?),
+                    '!' => Regex::negative_lookahead(self.alternatives()?),
+                    '<' if self.eat('=') => Regex::lookbehind(self.alternatives()?),
+                    '<' if self.eat('!') => Regex::negative_lookbehind(self.alternatives()?),
+                    '<' => {
+                        let name = self.name()?;
+                        Regex::named_group(&name, self.alternatives()?)
+                    }
+                    found => return Err(PatternError::UnexpectedChar { position, found })
+                }
+            }
+        };
+        self.expect(')')?;
+        Ok(regex)
+    }
+
+    /// Parses the rest of a class, after its opening bracket.
+    fn class<'a>(&mut self) -> Result<Regex<'a>, PatternError> {
+        let negated = self.eat('^');
+        let mut ranges: Vec<(char, char)> = vec![];
+        let mut first = true;
+
+        loop {
+            let position = self.position;
+            let start = match self.next("`]`")? {
+                ']' if !first => break,
+                '\\' => self.class_escape()?,
+                c => ClassItem::Char(c)
+            };
+            first = false;
+
+            let start = match start {
+                ClassItem::Char(c) => c,
+                ClassItem::Ranges(escaped) => {
+                    ranges.extend(escaped);
+                    continue;
+                }
+            };
+
+            if self.peek() == Some('-') && !matches!(self.chars.get(self.position + 1), None | Some(']')) {
+                self.position += 1;
+                let end = match self.next("a char")? {
+                    '\\' => self.class_escape()?,
+                    c => ClassItem::Char(c)
+                };
+                match end {
+                    ClassItem::Char(end) if start <= end => ranges.push((start, end)),
+                    ClassItem::Char(end) => return Err(PatternError::InvalidRange { position, start, end }),
+                    ClassItem::Ranges(_) => return Err(PatternError::UnexpectedChar { position: self.position - 1, found: self.chars[self.position - 1] })
+                }
+            } else {
+                ranges.push((start, start));
+            }
+        }
+
+        let class = match negated {
+            true => Class::new(&ranges).complement(),
+            false => Class::new(&ranges)
+        };
+        Ok(Regex::matcher(class))
+    }
+
+    /// Parses the rest of an escape sequence outside of a class, after its backslash.
+    fn escape<'a>(&mut self) -> Result<Atom<'a>, PatternError> {
+        match self.peek() {
+            Some('1'..='9') => return Ok(Atom::Regex(Regex::backreference(self.number()?))),
+            Some('k') => {
+                self.position += 1;
+                self.expect('<')?;
+                return Ok(Atom::Regex(Regex::named_backreference(&self.name()?)));
+            }
+            _ => {}
+        }
+
+        Ok(match self.class_escape()? {
+            ClassItem::Char(c) => Atom::Char(c),
+            ClassItem::Ranges(ranges) => Atom::Regex(Regex::matcher(Class::new(&ranges)))
+        })
+    }
+
+    /// Parses an escape sequence that can be used inside a class, after its backslash.
+    fn class_escape(&mut self) -> Result<ClassItem, PatternError> {
+        let position = self.position;
+        Ok(match self.next("an escaped char")? {
+            'd' => ClassItem::Ranges(Class::digit().ranges().to_vec()),
+            'D' => ClassItem::Ranges(Class::digit().complement().ranges().to_vec()),
+            'w' => ClassItem::Ranges(Class::word().ranges().to_vec()),
+            'W' => ClassItem::Ranges(Class::word().complement().ranges().to_vec()),
+            's' => ClassItem::Ranges(Class::whitespace().ranges().to_vec()),
+            'S' => ClassItem::Ranges(Class::whitespace().complement().ranges().to_vec()),
+            'n' => ClassItem::Char('\n'),
+            'r' => ClassItem::Char('\r'),
+            't' => ClassItem::Char('\t'),
+            c if c.is_alphanumeric() => return Err(PatternError::UnknownEscape { position, escape: c }),
+            c => ClassItem::Char(c)
+        })
+    }
+
+    /// Parses a repeat after an atom, if there is one.
+    fn repeat(&mut self) -> Result<Option<Between>, PatternError> {
+        let position = self.position;
+        let repeat = match self.peek() {
+            Some('*') => Between::new(0, None),
+            Some('+') => Between::new(1, None),
+            Some('?') => Between::new(0, Some(1)),
+            Some('{') => {
+                self.position += 1;
+                let minimum = self.number()?;
+                let maximum = match self.eat(',') {
+                    false => Some(minimum),
+                    true if self.peek() == Some('}') => None,
+                    true => Some(self.number()?)
+                };
+                self.expect('}')?;
+                if maximum.is_some_and(|maximum| maximum < minimum) {
+                    return Err(PatternError::InvalidRepeat(position));
+                }
+                self.position -= 1;
+                Between::new(minimum, maximum)
+            }
+            _ => return Ok(None)
+        };
+        self.position += 1;
+
+        match self.peek() {
+            Some(found) if matches!(found, '*' | '+' | '?' | '{') => Err(PatternError::NothingToRepeat { position: self.position, found }),
+            _ => Ok(Some(repeat))
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, PatternError> {
+        let position = self.position;
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            digits.push(c);
+            self.position += 1;
+        }
+
+        match (digits.is_empty(), self.peek()) {
+            (true, Some(found)) => Err(PatternError::UnexpectedChar { position, found }),
+            (true, None) => Err(PatternError::UnexpectedEnd(String::from("a number"))),
+            (false, _) => digits.parse().map_err(|_| PatternError::InvalidNumber(position))
+        }
+    }
+
+    /// Parses a group name and the `>` after it.
+    fn name(&mut self) -> Result<String, PatternError> {
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+            self.position += 1;
+        }
+        if name.is_empty() {
+            return match self.peek() {
+                Some(found) => Err(PatternError::UnexpectedChar { position: self.position, found }),
+                None => Err(PatternError::UnexpectedEnd(String::from("a name")))
+            };
+        }
+        self.expect('>')?;
+        Ok(name)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<char, PatternError> {
+        let c = self.peek().ok_or_else(|| PatternError::UnexpectedEnd(String::from(expected)))?;
+        self.position += 1;
+        Ok(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), PatternError> {
+        match self.next(&format!("`{}`", c))? {
+            found if found == c => Ok(()),
+            found => Err(PatternError::UnexpectedChar { position: self.position - 1, found })
+        }
+    }
+}
+
+/// Everything that can be wrong with a pattern. Positions are char indexes, starting at 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern ended, although the given thing was expected.
+    UnexpectedEnd(String),
+    /// The char at the given position is not allowed there.
+    UnexpectedChar { position: usize, found: char },
+    /// The repeat char at the given position does not follow anything that could repeat.
+    NothingToRepeat { position: usize, found: char },
+    /// The class range at the given position ends before it starts.
+    InvalidRange { position: usize, start: char, end: char },
+    /// The repeat at the given position has a maximum below its minimum.
+    InvalidRepeat(usize),
+    /// The number at the given position is too large.
+    InvalidNumber(usize),
+    /// The escape sequence at the given position is unknown.
+    UnknownEscape { position: usize, escape: char },
+}
+
+impl std::error::Error for PatternError {}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd(expected) => write!(f, "at the end: expected {}", expected),
+            Self::UnexpectedChar { position, found } => write!(f, "at column {}: unexpected `{}`", position + 1, found),
+            Self::NothingToRepeat { position, found } => write!(f, "at column {}: nothing to repeat before `{}`", position + 1, found),
+            Self::InvalidRange { position, start, end } => write!(f, "at column {}: the range {}-{} ends before it starts", position + 1, start, end),
+            Self::InvalidRepeat(position) => write!(f, "at column {}: the repeat has a maximum below its minimum", position + 1),
+            Self::InvalidNumber(position) => write!(f, "at column {}: the number is too large", position + 1),
+            Self::UnknownEscape { position, escape } => write!(f, "at column {}: unknown escape sequence `\\{}`", position + 1, escape)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::pattern::PatternError;
+    use crate::regex::Regex;
+
+    #[test]
+    fn success_parse() {
+        let regex = Regex::parse(r"a(b|cd)*\.[x-z\d]?").unwrap();
+
+        assert!(regex.matches("a."));
+        assert!(regex.matches("abcdb.7"));
+        assert!(regex.matches("a.y"));
+        assert!(!regex.matches("ac."));
+        assert!(!regex.matches("a.w"))
+    }
+
+    #[test]
+    fn success_parse_repeats() {
+        let regex = Regex::parse("x{2}y{1,}z{0,2}").unwrap();
+
+        assert!(regex.matches("xxyyyzz"));
+        assert!(!regex.matches("xyz"));
+        assert!(!regex.matches("xxyzzz"))
+    }
+
+    #[test]
+    fn success_parse_negated_class_and_empty_alternative() {
+        let regex = Regex::parse(r"[^\s,]+(,|)").unwrap();
+
+        assert!(regex.matches("abc,"));
+        assert!(regex.matches("abc"));
+        assert!(!regex.matches("a c"))
+    }
+
+    #[test]
+    fn success_parse_groups_and_backreferences() {
+        let regex = Regex::parse(r"(?<quote>['])(?:\w)*\k<quote>-(a)\2").unwrap();
+
+        assert!(regex.matches("'ab'-aa"));
+        assert_eq!(Some(String::from("a")), regex.captures("''-aa").unwrap().unwrap().get(2))
+    }
+
+    #[test]
+    fn success_parse_anchors_and_lookarounds() {
+        let regex = Regex::parse(r"^foo(?!bar)").unwrap();
+
+        assert_eq!(Some((0, 3)), regex.find("foobaz").unwrap());
+        assert_eq!(None, regex.find("foobar").unwrap());
+        assert_eq!(None, regex.find("xfoo").unwrap())
+    }
+
+    #[test]
+    fn failure_parse() {
+        assert_eq!(Err(PatternError::UnexpectedEnd(String::from("`)`"))), Regex::parse("(ab").map(|_| ()));
+        assert_eq!(Err(PatternError::UnexpectedChar { position: 2, found: ')' }), Regex::parse("ab)").map(|_| ()));
+        assert_eq!(Err(PatternError::NothingToRepeat { position: 0, found: '+' }), Regex::parse("+a").map(|_| ()));
+        assert_eq!(Err(PatternError::InvalidRange { position: 1, start: 'z', end: 'a' }), Regex::parse("[z-a]").map(|_| ()));
+        assert_eq!(Err(PatternError::InvalidRepeat(1)), Regex::parse("a{3,2}").map(|_| ()));
+        assert_eq!(Err(PatternError::UnknownEscape { position: 1, escape: 'q' }), Regex::parse(r"\q").map(|_| ()))
+    }
+}
@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+
+use crate::regex::{Regex, Spans};
+use crate::regex::captures::Captures;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+
+impl<'a> Regex<'a> {
+    /// Searches the given string for the longest part this Regex matches at the leftmost
+    /// position, like POSIX regexec does, and returns its start and end char index.
+    /// Unlike find, every way of matching is tried, which can take much longer.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::parse("in|int|integer").unwrap();
+    ///
+    /// assert_eq!(Some((3, 5)), regex.find("an integer").unwrap());
+    /// assert_eq!(Some((3, 10)), regex.find_longest("an integer").unwrap());
+    /// ```
+    pub fn find_longest(&self, string: &str) -> Result<Option<(usize, usize)>, MatchError> {
+        Ok(self.find_longest_captures(string)?.and_then(|captures| captures.span(0)))
+    }
+
+    /// Searches the given string like find_longest and returns the parts its groups captured,
+    /// with the whole match as group 0. Of all ways to match the longest part, the groups
+    /// follow the POSIX rules: going by their number, each group starts as early and then
+    /// ends as late as possible. A repeated group captures its last repeat.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = Regex::parse("(a|ab)(c|bcd)(d*)").unwrap();
+    /// let captures = regex.find_longest_captures("abcd").unwrap().unwrap();
+    ///
+    /// assert_eq!(Some(String::from("ab")), captures.get(1));
+    /// assert_eq!(Some(String::from("c")), captures.get(2));
+    /// assert_eq!(Some(String::from("d")), captures.get(3));
+    /// ```
+    pub fn find_longest_captures(&self, string: &str) -> Result<Option<Captures>, MatchError> {
+        let mut state = MatchState::new(string, self.recursion_limit).with_groups_of(self);
+        for start in self.candidate_starts(string) {
+            if let Some((end, spans)) = self.longest_match_at(&mut state, start)? {
+                return Ok(Some(state.captures_of(string, spans, (start, end))));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Matches this Regex beginning at the given position along every possible way and returns
+    /// where the longest match ends, together with the spans its groups captured.
+    /// The groups of this Regex must already be set on the MatchState.
+    fn longest_match_at(&self, state: &mut MatchState, position: usize) -> Result<Option<(usize, Spans)>, MatchError> {
+        state.clear_captures();
+        state.cursor.set_index(position);
+
+        let mut longest: Option<(usize, Spans)> = None;
+        self.matches_string(state, &mut |state| {
+            let end = state.cursor.index();
+            let spans = state.capture_spans();
+            let preferred = match &longest {
+                Some((longest_end, longest_spans)) => end.cmp(longest_end).then_with(|| posix_order(spans, longest_spans)) == Ordering::Greater,
+                None => true
+            };
+
+            if preferred {
+                longest = Some((end, spans.to_vec()));
+            }
+            Ok(false)
+        })?;
+        Ok(longest)
+    }
+}
+
+/// Compares the spans of two ways to match the same part by the POSIX rules.
+/// Greater means the first one is preferred.
+fn posix_order(spans: &[Option<(usize, usize)>], other_spans: &[Option<(usize, usize)>]) -> Ordering {
+    spans.iter().zip(other_spans).skip(1)
+        .map(|(span, other_span)| match (span, other_span) {
+            (Some((start, end)), Some((other_start, other_end))) => other_start.cmp(start).then(end.cmp(other_end)),
+            (span, other_span) => span.is_some().cmp(&other_span.is_some())
+        })
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::regex::Regex;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_find_longest() {
+        let regex = Regex::from("a") | (Regex::from("a") + Regex::matcher(Any).that_repeats(ZeroToInfinity) + "z");
+
+        assert_eq!(Some((1, 2)), regex.find("xabz az").unwrap());
+        assert_eq!(Some((1, 7)), regex.find_longest("xabz az").unwrap());
+        assert_eq!(None, regex.find_longest("xyz").unwrap())
+    }
+
+    #[test]
+    fn success_find_longest_captures_prefers_earlier_groups() {
+        let regex = Regex::parse("(a*)(a*)").unwrap();
+        let captures = regex.find_longest_captures("aaa").unwrap().unwrap();
+
+        assert_eq!(Some((0, 3)), captures.span(1));
+        assert_eq!(Some((3, 3)), captures.span(2))
+    }
+
+    #[test]
+    fn success_find_longest_captures_last_repeat() {
+        let regex = Regex::parse("(a|ab|b)*").unwrap();
+        let captures = regex.find_longest_captures("abab").unwrap().unwrap();
+
+        assert_eq!(Some((0, 4)), captures.span(0));
+        assert_eq!(Some(String::from("ab")), captures.get(1))
+    }
+
+    #[test]
+    fn success_find_longest_captures_participating_group() {
+        let regex = Regex::parse("(x)?x?y").unwrap();
+        let captures = regex.find_longest_captures("xy").unwrap().unwrap();
+
+        assert_eq!(Some(String::from("x")), captures.get(1))
+    }
+}
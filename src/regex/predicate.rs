@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
+
+/// Matches a single token the predicate accepts. Only cursors over tokens have any,
+/// so it never matches text.
+pub struct Predicate<F> {
+    predicate: F
+}
+
+impl<'a, T: 'a, F: Fn(&T) -> bool + 'a> RegexType<'a, T> for Predicate<F> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let start = state.cursor.index();
+        let matches = match state.cursor.next_token() {
+            Some(token) => (self.predicate)(token),
+            None => {
+                if state.cursor.at_the_end() {
+                    state.expect_more_input(|| None);
+                }
+                false
+            }
+        };
+
+        match matches {
+            true => next(state),
+            false => {
+                state.expect(start, || String::from("a matching token"));
+                Ok(false)
+            }
+        }
+    }
+
+    fn nullable(&self, _nullable_rules: &HashMap<String, bool>) -> bool {
+        false
+    }
+
+    fn single_end(&self) -> bool {
+        true
+    }
+
+    fn simplify(self: Box<Self>, _stats: &mut SimplifyStats) -> Regex<'a, T> {
+        Regex::new_regex(*self)
+    }
+}
+
+impl<F> Predicate<F> {
+    pub fn new(predicate: F) -> Self {
+        Predicate {
+            predicate
+        }
+    }
+}
+
+impl<F> Display for Predicate<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Token")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::Regex;
+    use crate::repeat::between::Between;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[derive(Debug, PartialEq)]
+    enum Token {
+        Number(i64),
+        Plus,
+        Minus,
+    }
+
+    fn number<'a>() -> Regex<'a, Token> {
+        Regex::token(|token| matches!(token, Token::Number(_)))
+    }
+
+    #[test]
+    fn success_matches_tokens() {
+        let operator = Regex::token(|token| *token == Token::Plus) | Regex::token(|token| *token == Token::Minus);
+        let sum = number() + (operator + number()).that_repeats(ZeroToInfinity);
+
+        assert!(sum.matches_tokens(&[Token::Number(1), Token::Plus, Token::Number(2), Token::Minus, Token::Number(3)]));
+        assert!(sum.matches_tokens(&[Token::Number(1)]));
+        assert!(!sum.matches_tokens(&[Token::Number(1), Token::Plus]));
+        assert!(!sum.matches_tokens(&[]))
+    }
+
+    #[test]
+    fn success_matches_tokens_with_backreference() {
+        let small = Regex::token(|n: &i64| *n < 10).that_repeats(Between::new(1, Some(3)));
+        let regex = Regex::group(small) + Regex::token(|n| *n >= 10) + Regex::backreference(1);
+
+        assert!(regex.matches_tokens(&[1, 2, 50, 1, 2]));
+        assert!(!regex.matches_tokens(&[1, 2, 50, 2, 1]))
+    }
+
+    #[test]
+    fn success_find_tokens() {
+        let regex = Regex::token(|n: &i64| *n < 0).that_repeats(Between::new(2, None));
+
+        assert_eq!(Ok(Some((3, 6))), regex.find_tokens(&[1, -1, 2, -2, -3, -4, 5]));
+        assert_eq!(Ok(None), regex.find_tokens(&[-1, 1]))
+    }
+
+    #[test]
+    fn failure_token_does_not_match_text() {
+        let regex: Regex<char> = Regex::token(|_| true);
+
+        assert!(!regex.matches("a"));
+        assert!(regex.matches_tokens(&['a']))
+    }
+}
@@ -1,7 +1,100 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::string_pointer::{StringPointer, StringPointerError};
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::group::Group;
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+use crate::regex::sequence::Sequence;
+use crate::regex::simplify::SimplifyStats;
 
-pub trait RegexType: Display {
-    fn matches_string(&self, string_pointer: &mut StringPointer) -> Result<bool, StringPointerError>;
-}
\ No newline at end of file
+/// The rest of a match. It gets called with the MatchState at every position
+/// a RegexType could end at and returns if the remaining regex matches from there.
+pub type Continuation<'c, T = char> = dyn FnMut(&mut MatchState<T>) -> Result<bool, MatchError> + 'c;
+
+/// A kind of regex node. T is the type of the tokens it matches, which is char for text.
+pub trait RegexType<'a, T: 'a = char>: Display {
+    /// Matches this type at the current position of the MatchState. Every
+    /// possible end position is handed to the given continuation, until one is accepted.
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError>;
+
+    /// Returns the regexes this type consists of.
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        vec![]
+    }
+
+    /// Returns if this type can match without consuming a char.
+    /// The given map tells which rules can do so.
+    fn nullable(&self, _nullable_rules: &HashMap<String, bool>) -> bool {
+        true
+    }
+
+    /// Returns the names of all rules this type might call before consuming a char.
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        self.children().into_iter()
+            .flat_map(|child| child.leading_rules(nullable_rules))
+            .collect()
+    }
+
+    /// Returns the literal text every match of this type must contain.
+    fn literals(&self) -> Literals {
+        Literals::unknown()
+    }
+
+    /// Returns an equivalent, simpler regex for this type. The result gets the repeat
+    /// of the regex this type belonged to.
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T>;
+
+    /// Adds a fragment matching the same strings as this type to the given Nfa.
+    /// Fails for types a finite automaton cannot express.
+    fn to_nfa(&self, _nfa: &mut Nfa, _alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        Err(AutomatonError::Unsupported(self.to_string()))
+    }
+
+    /// Appends a random string this type matches to the given output.
+    /// Fails for types that cannot be generated on their own.
+    fn generate(&self, _generator: &mut Generator, _output: &mut String) -> Result<(), GenerateError> {
+        Err(GenerateError::Unsupported(self.to_string()))
+    }
+
+    /// Returns the one string this type matches, if it is a plain literal.
+    fn literal(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the chars this type matches as inclusive ranges, if it checks exactly one char.
+    fn chars(&self) -> Option<Vec<(char, char)>> {
+        None
+    }
+
+    /// Returns if this type hands one end position at most to its continuation, wherever it
+    /// starts, unless matching approximately. Repeats of such types need no recursion.
+    fn single_end(&self) -> bool {
+        false
+    }
+
+    /// Returns if this type is a Sequence.
+    fn is_sequence(&self) -> bool {
+        false
+    }
+
+    /// Returns this type as a Sequence that can be appended to, if it is one.
+    fn sequence_mut(&mut self) -> Option<&mut Sequence<'a, T>> {
+        None
+    }
+
+    /// Returns the name of the rule this type refers to, if it is a rule reference.
+    fn rule_reference(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns this type as a Group, if it is one.
+    fn group(&self) -> Option<&Group<'a, T>> {
+        None
+    }
+}
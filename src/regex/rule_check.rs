@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::regex::rules::RuleError;
+
+/// What a named rule is made of, as far as the checks on a set of rules need to know.
+/// Both the rules of a RuleSet and the rules of a Grammar are checked this way.
+pub(crate) trait RuleBody {
+    /// Returns the names of all rules this body refers to.
+    fn referenced_rules(&self) -> Vec<String>;
+
+    /// Returns if this body can match without consuming a char.
+    /// The given map tells which rules can do so.
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool;
+
+    /// Returns the names of all rules this body might call before consuming a char.
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String>;
+}
+
+/// Makes sure no name is defined twice, every referenced rule exists
+/// and no rule can call itself again without consuming a char.
+pub(crate) fn check_rules<R: RuleBody>(rules: &[(String, R)]) -> Result<(), RuleError> {
+    check_names(rules)?;
+    check_left_recursion(rules)
+}
+
+/// Makes sure no name is defined twice and every referenced rule exists.
+fn check_names<R: RuleBody>(rules: &[(String, R)]) -> Result<(), RuleError> {
+    for (index, (name, _)) in rules.iter().enumerate() {
+        if rules[..index].iter().any(|(other_name, _)| other_name == name) {
+            return Err(RuleError::DuplicateRule(name.clone()));
+        }
+    }
+
+    for (name, body) in rules {
+        if let Some(reference) = body.referenced_rules().into_iter().find(|reference| rules.iter().all(|(other_name, _)| other_name != reference)) {
+            return Err(RuleError::UnknownRule { rule: name.clone(), reference });
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes sure no rule can reach itself without consuming a char.
+fn check_left_recursion<R: RuleBody>(rules: &[(String, R)]) -> Result<(), RuleError> {
+    let nullable_rules = nullable_rules(rules);
+    let leading_rules: HashMap<&str, Vec<String>> = rules.iter()
+        .map(|(name, body)| (name.as_str(), body.leading_rules(&nullable_rules)))
+        .collect();
+
+    for (name, _) in rules {
+        let mut path = vec![name.clone()];
+        if reaches(name, &leading_rules, &mut path) {
+            return Err(RuleError::LeftRecursion(path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns if the last rule on the path can reach the given target through leading rules.
+/// The path then ends with the target.
+fn reaches(target: &str, leading_rules: &HashMap<&str, Vec<String>>, path: &mut Vec<String>) -> bool {
+    let current = path[path.len() - 1].clone();
+    for leading in leading_rules.get(current.as_str()).into_iter().flatten() {
+        if leading == target {
+            path.push(leading.clone());
+            return true;
+        }
+        if path.contains(leading) {
+            continue;
+        }
+
+        path.push(leading.clone());
+        if reaches(target, leading_rules, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
+}
+
+/// Computes which rules can match without consuming a char, by repeating
+/// the computation until nothing changes anymore.
+fn nullable_rules<R: RuleBody>(rules: &[(String, R)]) -> HashMap<String, bool> {
+    let mut nullable_rules: HashMap<String, bool> = rules.iter()
+        .map(|(name, _)| (name.clone(), false))
+        .collect();
+
+    loop {
+        let mut changed = false;
+        for (name, body) in rules {
+            if !nullable_rules[name] && body.nullable(&nullable_rules) {
+                nullable_rules.insert(name.clone(), true);
+                changed = true;
+            }
+        }
+        if !changed {
+            return nullable_rules;
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::Regex;
+use crate::regex::simplify::SimplifyStats;
+
+/// Matches like the rule with the given name in the RuleSet the match was started with.
+/// This allows regexes to refer to themselves.
+pub struct RuleReference {
+    name: String
+}
+
+impl<'a> RegexType<'a> for RuleReference {
+    fn matches_string(&self, state: &mut MatchState, next: &mut Continuation) -> Result<bool, MatchError> {
+        let rule = state.rule(&self.name)?;
+        rule.matches_string(state, next)
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        nullable_rules.get(&self.name).copied().unwrap_or(false)
+    }
+
+    fn leading_rules(&self, _nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn rule_reference(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    fn simplify(self: Box<Self>, _stats: &mut SimplifyStats) -> Regex<'a> {
+        Regex::new_regex(*self)
+    }
+}
+
+impl RuleReference {
+    pub fn new(name: &str) -> Self {
+        RuleReference {
+            name: String::from(name)
+        }
+    }
+}
+
+impl Display for RuleReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Rule <{}>", self.name)
+    }
+}
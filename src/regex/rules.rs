@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::fmt::Formatter;
+
+use crate::regex::Regex;
+use crate::regex::captures::Captures;
+use crate::regex::group::Group;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::{MatchError, MatchOutcome};
+use crate::regex::rule_check::{check_rules, RuleBody};
+
+/// A table of named regexes, which can refer to each other and to themselves
+/// with Regex::rule. This makes nested structures like balanced parentheses matchable.
+///
+/// Rules that could call themselves again without consuming a char (left recursion)
+/// are rejected when the RuleSet is created, so matching always terminates.
+///
+/// Examples:
+/// ```
+/// use crate::readex::regex::Regex;
+/// use crate::readex::regex::rules::RuleSet;
+/// use crate::readex::repeat::zero_to_infinity::ZeroToInfinity;
+///
+/// let rules = RuleSet::new(vec![
+///     ("balanced", (Regex::from("(") + Regex::rule("balanced") + ")").that_repeats(ZeroToInfinity)),
+/// ]).unwrap();
+///
+/// assert!(rules.matches("balanced", "(()(()))"));
+/// assert!(!rules.matches("balanced", "(()"));
+/// ```
+pub struct RuleSet<'a> {
+    rules: Vec<(String, Regex<'a>)>,
+}
+
+impl<'a> RuleSet<'a> {
+    pub fn new(rules: Vec<(&str, Regex<'a>)>) -> Result<Self, RuleError> {
+        let rule_set = RuleSet {
+            rules: rules.into_iter().map(|(name, regex)| (String::from(name), regex)).collect()
+        };
+
+        check_rules(&rule_set.rules)?;
+        Ok(rule_set)
+    }
+
+    /// Returns the rule with the given name.
+    pub fn get(&self, name: &str) -> Option<&Regex<'a>> {
+        self.rules.iter()
+            .find(|(rule_name, _)| rule_name == name)
+            .map(|(_, regex)| regex)
+    }
+
+    /// Returns if the rule with the given name matches the whole given string.
+    /// Errors while matching count as no match, use try_matches to tell them apart.
+    pub fn matches(&self, rule: &str, string: &str) -> bool {
+        match self.try_matches(rule, string) {
+            Ok(outcome) => outcome.is_match(),
+            Err(_) => false
+        }
+    }
+
+    /// Returns if the rule with the given name matches the whole given string,
+    /// or the error that prevented the decision.
+    pub fn try_matches(&self, rule: &str, string: &str) -> Result<MatchOutcome, MatchError> {
+        let root = self.get(rule).ok_or_else(|| MatchError::UnknownRule(String::from(rule)))?;
+        let mut state = MatchState::new(string, root.recursion_limit).with_groups_of(root).with_rules(self);
+        root.matches_whole_string(&mut state).map(MatchOutcome::from)
+    }
+
+    /// Returns the parts of the given string captured by the groups of all rules,
+    /// or None if the rule with the given name does not match. The groups are numbered
+    /// in the order the rules were defined.
+    pub fn captures(&self, rule: &str, string: &str) -> Result<Option<Captures>, MatchError> {
+        let root = self.get(rule).ok_or_else(|| MatchError::UnknownRule(String::from(rule)))?;
+        let mut state = MatchState::new(string, root.recursion_limit).with_groups_of(root).with_rules(self);
+        match root.matches_whole_string(&mut state)? {
+            true => Ok(Some(state.captures(string))),
+            false => Ok(None)
+        }
+    }
+
+    /// Returns the groups of all rules, in the order the rules were defined.
+    pub(crate) fn groups(&self) -> Vec<&Group<'a>> {
+        self.rules.iter()
+            .flat_map(|(_, regex)| regex.groups())
+            .collect()
+    }
+}
+
+impl<'a> RuleBody for Regex<'a> {
+    fn referenced_rules(&self) -> Vec<String> {
+        self.nodes().into_iter()
+            .filter_map(|node| node.regex_type.rule_reference())
+            .map(String::from)
+            .collect()
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        Regex::nullable(self, nullable_rules)
+    }
+
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        Regex::leading_rules(self, nullable_rules)
+    }
+}
+
+/// Everything that makes a set of rules invalid, for a RuleSet as well as for a Grammar.
+#[derive(Debug, PartialEq)]
+pub enum RuleError {
+    DuplicateRule(String),
+    UnknownRule { rule: String, reference: String },
+    /// The rules on the path call each other without consuming a char.
+    LeftRecursion(Vec<String>),
+}
+
+impl std::error::Error for RuleError {}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateRule(name) => write!(f, "The rule {} is defined twice!", name),
+            Self::UnknownRule { rule, reference } => write!(f, "The rule {} refers to the unknown rule {}!", rule, reference),
+            Self::LeftRecursion(path) => write!(f, "Left recursion between rules: {}!", path.join(" -> "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::regex::outcome::MatchError;
+    use crate::regex::Regex;
+    use crate::regex::rules::{RuleError, RuleSet};
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_nested_brackets() {
+        let rules = RuleSet::new(vec![
+            ("value", Regex::from("x") | Regex::rule("list")),
+            ("list", Regex::from("[") + Regex::rule("values") + "]"),
+            ("values", Regex::from("") | (Regex::rule("value") + (Regex::from(",") + Regex::rule("value")).that_repeats(ZeroToInfinity))),
+        ]).unwrap();
+
+        assert!(rules.matches("value", "[x,[x,[]],x]"));
+        assert!(rules.matches("list", "[]"));
+        assert!(!rules.matches("value", "[x,[x]"));
+        assert!(!rules.matches("value", "[x,]"))
+    }
+
+    #[test]
+    fn success_nested_comments() {
+        let content = Regex::negative_lookahead(Regex::from("/*") | "*/") + Regex::matcher(Any);
+        let rules = RuleSet::new(vec![
+            ("comment", Regex::from("/*") + (Regex::rule("comment") | content).that_repeats(ZeroToInfinity) + "*/"),
+        ]).unwrap();
+
+        assert!(rules.matches("comment", "/* a /* b */ c */"));
+        assert!(!rules.matches("comment", "/* a /* b */ c"))
+    }
+
+    #[test]
+    fn success_captures_across_rules() {
+        let rules = RuleSet::new(vec![
+            ("pair", Regex::named_group("key", Regex::rule("word")) + "=" + Regex::named_group("value", Regex::rule("word"))),
+            ("word", Regex::from("a") | "b"),
+        ]).unwrap();
+
+        let captures = rules.captures("pair", "a=b").unwrap().unwrap();
+        assert_eq!(Some(String::from("a")), captures.name("key"));
+        assert_eq!(Some(String::from("b")), captures.name("value"))
+    }
+
+    #[test]
+    fn failure_left_recursion() {
+        let result = RuleSet::new(vec![
+            ("sum", Regex::rule("product") + "+" + "x"),
+            ("product", (Regex::from("").that_repeats(ZeroToInfinity) + Regex::rule("sum")) | "x"),
+        ]);
+
+        assert_eq!(Some(RuleError::LeftRecursion(vec![String::from("sum"), String::from("product"), String::from("sum")])), result.err())
+    }
+
+    #[test]
+    fn failure_direct_left_recursion() {
+        let result = RuleSet::new(vec![
+            ("list", (Regex::rule("list") + "x") | "x"),
+        ]);
+
+        assert_eq!(Some(RuleError::LeftRecursion(vec![String::from("list"), String::from("list")])), result.err())
+    }
+
+    #[test]
+    fn failure_unknown_rule() {
+        let result = RuleSet::new(vec![
+            ("list", Regex::from("x") + Regex::rule("item")),
+        ]);
+
+        assert_eq!(Some(RuleError::UnknownRule { rule: String::from("list"), reference: String::from("item") }), result.err());
+        assert_eq!(Err(MatchError::UnknownRule(String::from("item"))), Regex::rule("item").try_matches("x"))
+    }
+
+    #[test]
+    fn failure_duplicate_rule() {
+        let result = RuleSet::new(vec![
+            ("x", Regex::from("x")),
+            ("x", Regex::from("y")),
+        ]);
+
+        assert_eq!(Some(RuleError::DuplicateRule(String::from("x"))), result.err())
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::automaton::alphabet::Alphabet;
+use crate::automaton::AutomatonError;
+use crate::automaton::nfa::{Fragment, Nfa};
+use crate::matcher::string::Str;
+use crate::regex::generator::{GenerateError, Generator};
+use crate::regex::literals::Literals;
+use crate::regex::match_state::MatchState;
+use crate::regex::outcome::MatchError;
+use crate::regex::Regex;
+use crate::regex::regex_type::{Continuation, RegexType};
+use crate::regex::simplify::SimplifyStats;
+
+/// Matches its regexes one after another. It allows to treat a whole chain
+/// of regexes as one, for example to repeat it.
+pub struct Sequence<'a, T = char> {
+    regexes: Vec<Regex<'a, T>>
+}
+
+impl<'a, T> RegexType<'a, T> for Sequence<'a, T> {
+    fn matches_string(&self, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        self.matches_from(0, state, next)
+    }
+
+    fn children(&self) -> Vec<&Regex<'a, T>> {
+        self.regexes.iter().collect()
+    }
+
+    fn single_end(&self) -> bool {
+        self.regexes.iter().all(Regex::has_single_end)
+    }
+
+    fn nullable(&self, nullable_rules: &HashMap<String, bool>) -> bool {
+        self.regexes.iter().all(|regex| regex.nullable(nullable_rules))
+    }
+
+    fn leading_rules(&self, nullable_rules: &HashMap<String, bool>) -> Vec<String> {
+        let mut leading_rules = vec![];
+        for regex in &self.regexes {
+            leading_rules.extend(regex.leading_rules(nullable_rules));
+            if !regex.nullable(nullable_rules) {
+                break;
+            }
+        }
+        leading_rules
+    }
+
+    fn literals(&self) -> Literals {
+        self.regexes.iter()
+            .map(|regex| regex.literals())
+            .reduce(Literals::concat)
+            .unwrap_or_else(|| Literals::exact(""))
+    }
+
+    fn to_nfa(&self, nfa: &mut Nfa, alphabet: &Alphabet) -> Result<Fragment, AutomatonError> {
+        let mut fragment = nfa.empty();
+        for regex in &self.regexes {
+            let next = regex.to_nfa(nfa, alphabet)?;
+            fragment = nfa.concat(fragment, next);
+        }
+        Ok(fragment)
+    }
+
+    fn generate(&self, generator: &mut Generator, output: &mut String) -> Result<(), GenerateError> {
+        for regex in &self.regexes {
+            regex.generate_into(generator, output)?;
+        }
+        Ok(())
+    }
+
+    fn simplify(self: Box<Self>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        let elements = self.regexes.into_iter()
+            .flat_map(Regex::into_elements)
+            .collect();
+        Regex::from_elements(elements)
+            .unwrap_or_else(|| Regex::matcher(Str::new("")))
+            .simplified(stats)
+    }
+
+    fn is_sequence(&self) -> bool {
+        true
+    }
+
+    fn sequence_mut(&mut self) -> Option<&mut Sequence<'a, T>> {
+        Some(self)
+    }
+}
+
+impl<'a, T> Sequence<'a, T> {
+    pub fn new(regexes: Vec<Regex<'a, T>>) -> Self {
+        Sequence {
+            regexes
+        }
+    }
+
+    /// Appends the given regex to the end.
+    pub fn push(&mut self, regex: Regex<'a, T>) {
+        self.regexes.push(regex)
+    }
+
+    /// Matches the regexes beginning with the given index, followed by the continuation.
+    /// Regexes that end at one position at most are matched one after another, only the
+    /// others get the rest of the sequence as their continuation. So long sequences do
+    /// not nest deeper than the regexes in them.
+    fn matches_from(&self, index: usize, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        match self.regexes.get(index) {
+            Some(regex) if state.edit_costs().is_some() || !regex.has_single_end() => {
+                regex.matches_string(state, &mut |state| self.matches_from(index + 1, state, next))
+            }
+            Some(_) => self.matches_single_ends_from(index, state, next),
+            None => next(state)
+        }
+    }
+
+    /// Matches the regexes that end at one position at most beginning with the given index,
+    /// then the rest of the sequence. The spans captured before are restored if that fails.
+    fn matches_single_ends_from(&self, index: usize, state: &mut MatchState<T>, next: &mut Continuation<T>) -> Result<bool, MatchError> {
+        let captures = state.capture_spans().to_vec();
+        let mut end = index;
+        while let Some(regex) = self.regexes.get(end).filter(|regex| regex.has_single_end()) {
+            if !regex.matches_string(state, &mut |_| Ok(true))? {
+                state.restore_captures(&captures);
+                return Ok(false);
+            }
+            end += 1;
+        }
+
+        let matches = self.matches_from(end, state, next)?;
+        if !matches {
+            state.restore_captures(&captures);
+        }
+        Ok(matches)
+    }
+}
+
+impl<'a, T> Display for Sequence<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sequence")
+    }
+}
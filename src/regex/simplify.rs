@@ -0,0 +1,258 @@
+use crate::matcher::class::Class;
+use crate::matcher::string::Str;
+use crate::regex::Regex;
+use crate::repeat::Repeat;
+
+/// Counts what simplifying a Regex changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimplifyStats {
+    /// Adjacent literals merged into one.
+    pub merged_literals: usize,
+    /// Repeats of exactly one time removed.
+    pub collapsed_repeats: usize,
+    /// Sequences dissolved into the surrounding chain.
+    pub flattened_sequences: usize,
+    /// Common literal prefixes moved out of alternatives.
+    pub factored_prefixes: usize,
+    /// Alternatives of single chars turned into a class.
+    pub merged_classes: usize,
+}
+
+impl SimplifyStats {
+    /// Returns the number of all changes.
+    pub fn total(&self) -> usize {
+        self.merged_literals + self.collapsed_repeats + self.flattened_sequences + self.factored_prefixes + self.merged_classes
+    }
+
+    /// Returns if simplifying changed anything.
+    pub fn changed(&self) -> bool {
+        self.total() > 0
+    }
+}
+
+impl<'a, T> Regex<'a, T> {
+    /// Rewrites this Regex into an equivalent, simpler one. Adjacent literals are merged,
+    /// repeats of one time removed, nested sequences flattened, common prefixes moved out
+    /// of alternatives and alternatives of single chars turned into classes.
+    ///
+    /// Examples:
+    /// ```
+    /// use crate::readex::regex::Regex;
+    ///
+    /// let regex = (Regex::from("foo") + "bar") | (Regex::from("foo") + "baz");
+    /// let (simplified, stats) = regex.simplify();
+    ///
+    /// assert!(simplified.matches("foobaz"));
+    /// assert_eq!(2, stats.merged_literals);
+    /// assert_eq!(1, stats.factored_prefixes);
+    /// assert_eq!(1, stats.merged_classes);
+    /// ```
+    pub fn simplify(self) -> (Regex<'a, T>, SimplifyStats) {
+        let mut stats = SimplifyStats::default();
+        let simplified = self.simplified(&mut stats);
+        (simplified, stats)
+    }
+
+    pub(crate) fn simplified(self, stats: &mut SimplifyStats) -> Regex<'a, T> {
+        let mut elements: Vec<Regex<'a, T>> = vec![];
+        // A sequence on its own has no surrounding chain to be flattened into
+        let chained = self.next.is_some();
+
+        for element in self.into_elements() {
+            let Regex { regex_type, repeat, .. } = element;
+            let is_sequence = regex_type.is_sequence();
+            let simplified = regex_type.simplify(stats);
+
+            let repeat = match repeat {
+                Some(repeat) if repeat.get_minimum() == Some(1) && repeat.get_maximum() == Some(1) => {
+                    stats.collapsed_repeats += 1;
+                    None
+                }
+                repeat => repeat
+            };
+
+            match repeat {
+                Some(repeat) => elements.push(simplified.with_repeat(repeat)),
+                None => {
+                    if is_sequence && chained {
+                        stats.flattened_sequences += 1;
+                    }
+                    elements.extend(simplified.into_elements())
+                }
+            }
+        }
+
+        let mut merged: Vec<Regex<'a, T>> = vec![];
+        for element in elements {
+            let literals = match merged.last() {
+                Some(last) => (last.plain_literal(), element.plain_literal()),
+                None => (None, None)
+            };
+
+            match literals {
+                (Some(last_literal), Some(literal)) => {
+                    stats.merged_literals += 1;
+                    merged.pop();
+                    merged.push(Regex::matcher(Str::new(&format!("{}{}", last_literal, literal))));
+                }
+                _ => merged.push(element)
+            }
+        }
+
+        Regex::from_elements(merged).unwrap_or_else(|| Regex::matcher(Str::new("")))
+    }
+
+    /// Returns the literal this single Regex matches, if it has no repeat.
+    fn plain_literal(&self) -> Option<String> {
+        match &self.repeat {
+            None => self.regex_type.literal(),
+            Some(_) => None
+        }
+    }
+
+    /// Returns the chars this Regex matches, if it is a single, unrepeated Regex checking one char.
+    fn plain_chars(&self) -> Option<Vec<(char, char)>> {
+        match (&self.repeat, &self.next) {
+            (None, None) => self.regex_type.chars(),
+            _ => None
+        }
+    }
+
+    /// Sets the given repeat. If this Regex is a chain or already repeats, it is wrapped first.
+    fn with_repeat(self, repeat: Box<dyn Repeat + 'a>) -> Regex<'a, T> {
+        let mut regex = match self.repeat {
+            Some(_) => Regex::sequence_of(vec![self]),
+            None => self
+        };
+        if regex.next.is_some() {
+            regex = Regex::sequence_of(vec![regex]);
+        }
+        regex.repeat = Some(repeat);
+        regex
+    }
+}
+
+/// Returns an equivalent, simpler regex for matching either the left or the right one.
+/// Both are expected to be simplified already.
+pub(crate) fn simplify_alternatives<'a, T>(left: Regex<'a, T>, right: Regex<'a, T>, stats: &mut SimplifyStats) -> Regex<'a, T> {
+    if let (Some(left_chars), Some(right_chars)) = (left.plain_chars(), right.plain_chars()) {
+        stats.merged_classes += 1;
+        let ranges: Vec<(char, char)> = left_chars.into_iter().chain(right_chars).collect();
+        return Regex::matcher(Class::new(&ranges));
+    }
+
+    let prefix = match (left.plain_literal(), right.plain_literal()) {
+        (Some(left_literal), Some(right_literal)) => common_prefix(&left_literal, &right_literal),
+        _ => String::new()
+    };
+    if prefix.is_empty() {
+        return Regex::or(left, right);
+    }
+
+    stats.factored_prefixes += 1;
+    let prefix_length = prefix.chars().count();
+    let alternatives = simplify_alternatives(strip_prefix(left, prefix_length), strip_prefix(right, prefix_length), stats);
+    Regex::matcher(Str::new(&prefix)).followed_by(alternatives)
+}
+
+/// Removes the given amount of chars from the literal at the start of the given chain.
+fn strip_prefix<T>(regex: Regex<'_, T>, length: usize) -> Regex<'_, T> {
+    let remainder: String = regex.plain_literal().unwrap_or_default().chars().skip(length).collect();
+
+    match (remainder.is_empty(), regex.next) {
+        (true, Some(next_regex)) => *next_regex,
+        (_, Some(next_regex)) => Regex::matcher(Str::new(&remainder)).followed_by(*next_regex),
+        (_, None) => Regex::matcher(Str::new(&remainder))
+    }
+}
+
+fn common_prefix(left: &str, right: &str) -> String {
+    left.chars().zip(right.chars())
+        .take_while(|(l, r)| l == r)
+        .map(|(l, _)| l)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::any::Any;
+    use crate::regex::Regex;
+    use crate::regex::simplify::SimplifyStats;
+    use crate::repeat::times::Times;
+    use crate::repeat::zero_to_infinity::ZeroToInfinity;
+
+    #[test]
+    fn success_merge_literals() {
+        let (regex, stats) = (Regex::from("foo") + "bar" + 'b' + "az").simplify();
+
+        assert_eq!(SimplifyStats { merged_literals: 3, ..Default::default() }, stats);
+        assert_eq!(Some(String::from("foobarbaz")), regex.literals().exact);
+        assert!(regex.next.is_none())
+    }
+
+    #[test]
+    fn success_collapse_times_one() {
+        let (regex, stats) = (Regex::from("foo").that_repeats(Times::new(1)) + "bar").simplify();
+
+        assert_eq!(1, stats.collapsed_repeats);
+        assert_eq!(1, stats.merged_literals);
+        assert!(regex.matches("foobar"))
+    }
+
+    #[test]
+    fn success_flatten_sequences() {
+        let inner = (Regex::from("a") + Regex::matcher(Any)).that_repeats(Times::new(1));
+        let (regex, stats) = (Regex::from("x") + inner + "b").simplify();
+
+        assert_eq!(SimplifyStats { merged_literals: 1, collapsed_repeats: 1, flattened_sequences: 1, ..Default::default() }, stats);
+        assert!(regex.matches("xa-b"));
+        assert!(!regex.matches("xab"))
+    }
+
+    #[test]
+    fn success_keep_repeated_sequences() {
+        let (regex, stats) = ((Regex::from("a") + "b").that_repeats(ZeroToInfinity) + "c").simplify();
+
+        assert_eq!(1, stats.merged_literals);
+        assert_eq!(0, stats.flattened_sequences);
+        assert!(regex.matches("ababc"));
+        assert!(!regex.matches("aabc"))
+    }
+
+    #[test]
+    fn success_factor_prefixes() {
+        let (regex, stats) = (Regex::from("foobar") | (Regex::from("foobaz") + Regex::matcher(Any))).simplify();
+
+        assert_eq!(1, stats.factored_prefixes);
+        assert_eq!("fooba", regex.literals().prefix);
+        assert!(regex.matches("foobar"));
+        assert!(regex.matches("foobaz!"));
+        assert!(!regex.matches("foobaz"))
+    }
+
+    #[test]
+    fn success_merge_classes() {
+        let (regex, stats) = (Regex::from('a') | 'b' | 'c' | 'x').simplify();
+
+        assert_eq!(3, stats.merged_classes);
+        assert_eq!(Some(vec![('a', 'c'), ('x', 'x')]), regex.plain_chars());
+        assert!(regex.matches("b"));
+        assert!(!regex.matches("d"))
+    }
+
+    #[test]
+    fn success_simplify_inside_groups() {
+        let (regex, stats) = (Regex::group(Regex::from("a") + "b") + Regex::backreference(1)).simplify();
+
+        assert_eq!(1, stats.merged_literals);
+        assert!(regex.matches("abab"))
+    }
+
+    #[test]
+    fn success_nothing_to_simplify() {
+        let (regex, stats) = Regex::matcher(Any).that_repeats(ZeroToInfinity).simplify();
+
+        assert!(!stats.changed());
+        assert!(regex.matches("anything"))
+    }
+}
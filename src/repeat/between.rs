@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter};
+
+use crate::repeat::Repeat;
+
+/// A Repeat with a minimum and an optional maximum of how often
+/// a char sequence should repeat. Without a maximum, it may repeat endlessly.
+pub struct Between {
+    minimum: usize,
+    maximum: Option<usize>,
+}
+
+impl Between {
+    pub fn new(minimum: usize, maximum: Option<usize>) -> Self {
+        Between {
+            minimum,
+            maximum
+        }
+    }
+}
+
+impl Repeat for Between {
+    fn get_minimum(&self) -> Option<usize> {
+        Some(self.minimum)
+    }
+
+    fn get_maximum(&self) -> Option<usize> {
+        self.maximum
+    }
+}
+
+impl Display for Between {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.maximum {
+            Some(maximum) => write!(f, "Between {} and {}", self.minimum, maximum),
+            None => write!(f, "At least {}", self.minimum)
+        }
+    }
+}
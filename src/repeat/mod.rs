@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+pub mod between;
 pub mod times;
 pub mod zero_to_infinity;
 
@@ -1,5 +1,6 @@
 use std::fmt::Formatter;
 
+use crate::cursor::Cursor;
 use crate::string_pointer::StringPointerError::NoCheckpointToReturn;
 
 pub type Result<T> = std::result::Result<T, StringPointerError>;
@@ -9,7 +10,7 @@ pub type Result<T> = std::result::Result<T, StringPointerError>;
 /// The check points enable an easy reset to a former location.
 pub struct StringPointer {
     index: usize,
-    string: String,
+    chars: Vec<char>,
     check_points: Vec<usize>,
 }
 
@@ -17,7 +18,7 @@ impl StringPointer {
     pub fn from(string: &str) -> Self {
         StringPointer {
             index: 0,
-            string: String::from(string),
+            chars: string.chars().collect(),
             check_points: vec![],
         }
     }
@@ -28,11 +29,44 @@ impl StringPointer {
         if self.max_index() < self.index + amount {
             return Err(StringPointerError::SizeExceeded)
         }
-        let result = String::from(&self.string[self.index..(amount + self.index)]);
+        let result = self.chars[self.index..(amount + self.index)].iter().collect();
         self.index += amount;
         Ok(result)
     }
 
+    /// Returns the current index of the pointer, counted in chars.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Moves the pointer to the given index, counted in chars.
+    /// Indexes behind the end of the string are clamped to the end.
+    pub fn set_index(&mut self, index: usize) {
+        self.index = index.min(self.max_index())
+    }
+
+    /// Returns the chars between the given start and end index.
+    /// Indexes behind the end of the string are clamped to the end.
+    pub fn slice(&self, start: usize, end: usize) -> String {
+        let end = end.min(self.max_index());
+        self.chars[start.min(end)..end].iter().collect()
+    }
+
+    /// Returns the char at the given index, if the string is long enough.
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.chars.get(index).copied()
+    }
+
+    /// Returns the length of the whole string, counted in chars.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Returns if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
     /// Sets the current index as a checkpoint.
     pub fn set_checkpoint(&mut self) {
         if !self.check_points.contains(&self.index) {
@@ -42,26 +76,66 @@ impl StringPointer {
 
     /// Resets the pointer to its former checkpoint, removing it in the process.
     pub fn return_to_checkpoint(&mut self) -> Result<()> {
-        if self.check_points.len() >= 1 {
-            let new_index = self.check_points.pop().unwrap();
-            self.index = new_index;
-            Ok(())
-        } else {
-            Err(NoCheckpointToReturn)
+        match self.check_points.pop() {
+            Some(new_index) => {
+                self.index = new_index;
+                Ok(())
+            }
+            None => Err(NoCheckpointToReturn)
         }
     }
 
     /// Returns if the pointer points to the end of the string.
     pub fn at_the_end(&self) -> bool {
-        self.index == self.string.len()
+        self.index == self.chars.len()
     }
 
     fn max_index(&self) -> usize {
-        self.string.len()
+        self.chars.len()
+    }
+}
+
+/// A StringPointer reads text, so it is a cursor for regexes of any token type,
+/// but only text matchers can match it.
+impl<T> Cursor<T> for StringPointer {
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn set_index(&mut self, index: usize) {
+        StringPointer::set_index(self, index)
+    }
+
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    fn next_token(&mut self) -> Option<&T> {
+        None
+    }
+
+    fn take_text(&mut self, amount: usize) -> Option<String> {
+        self.take_next(amount).ok()
+    }
+
+    fn text(&self, start: usize, end: usize) -> Option<String> {
+        Some(self.slice(start, end))
+    }
+
+    fn repeats(&mut self, start: usize, end: usize) -> bool {
+        let length = end.saturating_sub(start);
+        let repeated = self.chars.get(start..end);
+        let following = self.chars.get(self.index..self.index + length);
+
+        let matches = repeated.is_some() && repeated == following;
+        if matches {
+            self.index += length;
+        }
+        matches
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StringPointerError {
     SizeExceeded,
     NoCheckpointToReturn,
@@ -80,7 +154,7 @@ impl std::fmt::Display for StringPointerError {
 
 #[cfg(test)]
 mod tests {
-    use crate::string_pointer::{StringPointer, StringPointerError};
+    use crate::string_pointer::StringPointer;
     use crate::string_pointer::StringPointerError::{NoCheckpointToReturn, SizeExceeded};
 
     #[test]
@@ -122,9 +196,9 @@ mod tests {
     pub fn success_return_to_checkpoint() {
         let mut string_pointer = StringPointer::from("foobar");
 
-        let foo = string_pointer.take_next(3).unwrap();
+        string_pointer.take_next(3).unwrap();
         string_pointer.set_checkpoint();
-        let bar = string_pointer.take_next(3).unwrap();
+        string_pointer.take_next(3).unwrap();
         assert_eq!(vec![3], string_pointer.check_points);
         assert_eq!(6, string_pointer.index);
 
@@ -154,6 +228,15 @@ mod tests {
         assert!(string_pointer.at_the_end())
     }
 
+    #[test]
+    pub fn success_take_next_multibyte() {
+        let mut string_pointer = StringPointer::from("äöü");
+
+        assert_eq!("ä".to_string(), string_pointer.take_next(1).unwrap());
+        assert_eq!("öü".to_string(), string_pointer.take_next(2).unwrap());
+        assert!(string_pointer.at_the_end())
+    }
+
     #[test]
     pub fn failure_size_exceded() {
         let string = "foo";